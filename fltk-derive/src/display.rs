@@ -202,6 +202,18 @@ pub fn impl_display_trait(ast: &DeriveInput) -> TokenStream {
         format!("{}_{}", name_str, "in_selection").as_str(),
         name.span(),
     );
+    let style_buffer = Ident::new(
+        format!("{}_{}", name_str, "style_buffer").as_str(),
+        name.span(),
+    );
+    let style_table_count = Ident::new(
+        format!("{}_{}", name_str, "style_table_count").as_str(),
+        name.span(),
+    );
+    let style_table = Ident::new(
+        format!("{}_{}", name_str, "style_table").as_str(),
+        name.span(),
+    );
 
     let gen = quote! {
         unsafe impl DisplayExt for #name {
@@ -237,7 +249,7 @@ pub fn impl_display_trait(ast: &DeriveInput) -> TokenStream {
             fn set_text_font(&mut self, font: Font) {
                 assert!(!self.was_deleted());
                 assert!(self.buffer().is_some());
-                unsafe { #set_text_font(self._inner, font as i32) }
+                unsafe { #set_text_font(self._inner, font.bits()) }
             }
 
             fn text_color(&self) -> Color{
@@ -389,13 +401,17 @@ pub fn impl_display_trait(ast: &DeriveInput) -> TokenStream {
                 let mut colors: Vec<u32> = vec![];
                 let mut fonts: Vec<i32> = vec![];
                 let mut sizes: Vec<i32> = vec![];
+                let mut attrs: Vec<u32> = vec![];
+                let mut bgcolors: Vec<u32> = vec![];
                 for entry in entries.iter() {
                     colors.push(entry.color as u32);
-                    fonts.push(entry.font as i32);
+                    fonts.push(entry.font.bits());
                     sizes.push(entry.size as i32);
+                    attrs.push(entry.attr.bits() as u32);
+                    bgcolors.push(entry.bgcolor as u32);
                 }
                 unsafe {
-                    let x = #set_style_table_entry(self._inner, style_buffer.as_ptr() as *mut raw::c_void, &mut colors[0], &mut fonts[0], &mut sizes[0], entries.len() as i32);
+                    let x = #set_style_table_entry(self._inner, style_buffer.as_ptr() as *mut raw::c_void, &mut colors[0], &mut fonts[0], &mut sizes[0], &mut attrs[0], &mut bgcolors[0], entries.len() as i32);
                     StyleTables { _inner: x }
                 }
             }
@@ -576,7 +592,7 @@ pub fn impl_display_trait(ast: &DeriveInput) -> TokenStream {
             fn set_linenumber_font(&mut self, font: Font) {
                 unsafe {
                     assert!(!self.was_deleted());
-                    #set_linenumber_font(self._inner, font as i32)
+                    #set_linenumber_font(self._inner, font.bits())
                 }
             }
 
@@ -654,6 +670,53 @@ pub fn impl_display_trait(ast: &DeriveInput) -> TokenStream {
                     }
                 }
             }
+
+            fn style_buffer(&self) -> Option<TextBuffer> {
+                unsafe {
+                    assert!(!self.was_deleted());
+                    let buffer = #style_buffer(self._inner);
+                    if buffer.is_null() {
+                        None
+                    } else {
+                        Some(TextBuffer::from_ptr(buffer))
+                    }
+                }
+            }
+
+            fn highlight_data(&self) -> Vec<StyleTableEntry> {
+                assert!(!self.was_deleted());
+                unsafe {
+                    let sz = #style_table_count(self._inner) as usize;
+                    if sz == 0 {
+                        return vec![];
+                    }
+                    let mut colors: Vec<u32> = vec![0; sz];
+                    let mut fonts: Vec<i32> = vec![0; sz];
+                    let mut sizes: Vec<i32> = vec![0; sz];
+                    let mut attrs: Vec<u32> = vec![0; sz];
+                    let mut bgcolors: Vec<u32> = vec![0; sz];
+                    #style_table(self._inner, &mut colors[0], &mut fonts[0], &mut sizes[0], &mut attrs[0], &mut bgcolors[0]);
+                    (0..sz)
+                        .map(|i| StyleTableEntry {
+                            color: mem::transmute(colors[i]),
+                            font: mem::transmute(fonts[i]),
+                            size: sizes[i] as u32,
+                            attr: TextAttr::from_bits(attrs[i] as i32),
+                            bgcolor: mem::transmute(bgcolors[i]),
+                        })
+                        .collect()
+                }
+            }
+
+            fn append_styled(&mut self, text: &str, style: char) {
+                assert!(!self.was_deleted());
+                assert!(style.is_ascii());
+                let mut buffer = self.buffer().unwrap();
+                buffer.append(text);
+                if let Some(mut style_buffer) = self.style_buffer() {
+                    style_buffer.append(&style.to_string().repeat(text.len()));
+                }
+            }
         }
     };
     gen.into()
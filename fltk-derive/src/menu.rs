@@ -73,7 +73,7 @@ pub fn impl_menu_trait(ast: &DeriveInput) -> TokenStream {
                     unsafe extern "C" fn shim(_wid: *mut Fl_Widget, data: *mut raw::c_void) {
                         let a: *mut Box<dyn FnMut()> = data as *mut Box<dyn FnMut()>;
                         let f: &mut (dyn FnMut()) = &mut **a;
-                        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f()));
+                        crate::app::catch_callback_panic(|| f());
                     }
                     let a: *mut Box<dyn FnMut()> = Box::into_raw(Box::new(cb));
                     let data: *mut raw::c_void = a as *mut raw::c_void;
@@ -89,7 +89,7 @@ pub fn impl_menu_trait(ast: &DeriveInput) -> TokenStream {
                     unsafe extern "C" fn shim(_wid: *mut Fl_Widget, data: *mut raw::c_void) {
                         let a: *mut Box<dyn FnMut()> = data as *mut Box<dyn FnMut()>;
                         let f: &mut (dyn FnMut()) = &mut **a;
-                        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f()));
+                        crate::app::catch_callback_panic(|| f());
                     }
                     let a: *mut Box<dyn FnMut()> = Box::into_raw(Box::new(cb));
                     let data: *mut raw::c_void = a as *mut raw::c_void;
@@ -98,7 +98,7 @@ pub fn impl_menu_trait(ast: &DeriveInput) -> TokenStream {
                 }
             }
 
-            fn add_emit<T: 'static + Copy + Send + Sync>(
+            fn add_emit<T: 'static + Clone + Send + Sync>(
                 &mut self,
                 name: &str,
                 shortcut: Shortcut,
@@ -106,10 +106,10 @@ pub fn impl_menu_trait(ast: &DeriveInput) -> TokenStream {
                 sender: crate::app::Sender<T>,
                 msg: T,
             ) {
-                self.add(name, shortcut, flag, Box::new(move|| sender.send(msg)))
+                self.add(name, shortcut, flag, Box::new(move|| sender.send(msg.clone())))
             }
 
-            fn insert_emit<T: 'static + Copy + Send + Sync>(
+            fn insert_emit<T: 'static + Clone + Send + Sync>(
                 &mut self,
                 idx: u32,
                 name: &str,
@@ -118,7 +118,7 @@ pub fn impl_menu_trait(ast: &DeriveInput) -> TokenStream {
                 sender: crate::app::Sender<T>,
                 msg: T,
             ) {
-                self.insert(idx, name, shortcut, flag, Box::new(move|| sender.send(msg)))
+                self.insert(idx, name, shortcut, flag, Box::new(move|| sender.send(msg.clone())))
             }
 
             fn remove(&mut self, idx: u32) {
@@ -176,7 +176,7 @@ pub fn impl_menu_trait(ast: &DeriveInput) -> TokenStream {
             fn set_text_font(&mut self, c: Font) {
                 unsafe {
                     assert!(!self.was_deleted());
-                    #set_text_font(self._inner, c as i32)
+                    #set_text_font(self._inner, c.bits())
                 }
             }
 
@@ -608,7 +608,7 @@ pub fn impl_table_trait(ast: &DeriveInput) -> TokenStream {
                         let ctx: TableContext = mem::transmute(ctx);
                         let a: *mut Box<dyn FnMut(crate::table::TableContext, i32, i32, i32, i32, i32, i32)> = data as *mut Box<dyn FnMut(crate::table::TableContext, i32, i32, i32, i32, i32, i32)>;
                         let f: &mut (dyn FnMut(crate::table::TableContext, i32, i32, i32, i32, i32, i32)) = &mut **a;
-                        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f(ctx, arg2, arg3, arg4, arg5, arg6, arg7)));
+                        crate::app::catch_callback_panic(|| f(ctx, arg2, arg3, arg4, arg5, arg6, arg7));
                     }
                     self.unset_draw_cell_callback();
                     let a: *mut Box<dyn FnMut(crate::table::TableContext, i32, i32, i32, i32, i32, i32)> = Box::into_raw(Box::new(cb));
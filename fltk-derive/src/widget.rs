@@ -425,7 +425,7 @@ pub fn impl_widget_trait(ast: &DeriveInput) -> TokenStream {
 
             fn set_label_font(&mut self, font: Font) {
                 assert!(!self.was_deleted());
-                unsafe { #set_label_font(self._inner, font as i32) }
+                unsafe { #set_label_font(self._inner, font.bits()) }
             }
 
             fn label_size(&self) -> i32 {
@@ -542,7 +542,7 @@ pub fn impl_widget_trait(ast: &DeriveInput) -> TokenStream {
                     unsafe extern "C" fn shim(_wid: *mut fltk_sys::widget::Fl_Widget, data: *mut raw::c_void) {
                         let a: *mut Box<dyn FnMut()> = data as *mut Box<dyn FnMut()>;
                         let f: &mut (dyn FnMut()) = &mut **a;
-                        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f()));
+                        crate::app::catch_callback_panic(|| f());
                     }
                     self.unset_callback();
                     let a: *mut Box<dyn FnMut()> = Box::into_raw(Box::new(cb));
@@ -569,11 +569,11 @@ pub fn impl_widget_trait(ast: &DeriveInput) -> TokenStream {
                         let ev: Event = mem::transmute(_ev);
                         let a: *mut Box<dyn FnMut(Event) -> bool> = data as *mut Box<dyn FnMut(Event) -> bool>;
                         let f: &mut (dyn FnMut(Event) -> bool) = &mut **a;
-                        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| match f(ev) {
+                        let result = crate::app::catch_callback_panic(|| match f(ev) {
                             true => return 1,
                             false => return 0,
-                        }));
-                        if let Ok(ret) = result {
+                        });
+                        if let Some(ret) = result {
                             ret
                         } else {
                             0
@@ -592,7 +592,7 @@ pub fn impl_widget_trait(ast: &DeriveInput) -> TokenStream {
                     unsafe extern "C" fn shim(data: *mut raw::c_void) {
                         let a: *mut Box<dyn FnMut()> = data as *mut Box<dyn FnMut()>;
                         let f: &mut (dyn FnMut()) = &mut **a;
-                        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f()));
+                        crate::app::catch_callback_panic(|| f());
                     }
                     self.unset_draw_callback();
                     let a: *mut Box<dyn FnMut()> = Box::into_raw(Box::new(cb));
@@ -742,9 +742,9 @@ pub fn impl_widget_trait(ast: &DeriveInput) -> TokenStream {
                 }
             }
 
-            fn emit<T: 'static + Copy + Send + Sync>(&mut self, sender: crate::app::Sender<T>, msg: T) {
+            fn emit<T: 'static + Clone + Send + Sync>(&mut self, sender: crate::app::Sender<T>, msg: T) {
                 assert!(!self.was_deleted());
-                self.set_callback(Box::new(move || sender.send(msg)))
+                self.set_callback(Box::new(move || sender.send(msg.clone())))
             }
 
             unsafe fn user_data(&self) -> Option<Box<dyn FnMut()>> {
@@ -908,6 +908,21 @@ pub fn impl_widget_trait(ast: &DeriveInput) -> TokenStream {
                     Some(crate::group::Group::from_widget_ptr(ptr as *mut fltk_sys::widget::Fl_Widget))
                 }
             }
+
+            fn screen_xywh(&self) -> (i32, i32, i32, i32) {
+                assert!(!self.was_deleted());
+                // Widget coordinates are relative to the enclosing window, so only windows add offsets
+                let (mut x, mut y) = (self.x(), self.y());
+                let mut parent = self.parent();
+                while let Some(mut p) = parent {
+                    if p.as_window().is_some() {
+                        x += p.x();
+                        y += p.y();
+                    }
+                    parent = p.parent();
+                }
+                (x, y, self.width(), self.height())
+            }
         }
     };
     gen.into()
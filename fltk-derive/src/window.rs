@@ -43,6 +43,11 @@ pub fn impl_window_trait(ast: &DeriveInput) -> TokenStream {
     let set_region = Ident::new(format!("{}_{}", name_str, "set_region").as_str(), name.span());
     let iconize = Ident::new(format!("{}_{}", name_str, "iconize").as_str(), name.span());
     let fullscreen_active = Ident::new(format!("{}_{}", name_str, "fullscreen_active").as_str(), name.span());
+    let set_resize_callback = Ident::new(
+        format!("{}_{}", name_str, "set_resize_callback").as_str(),
+        name.span(),
+    );
+    let resize_data = Ident::new(format!("{}_{}", name_str, "resize_data").as_str(), name.span());
 
     let gen = quote! {
         unsafe impl WindowExt for #name {
@@ -201,6 +206,56 @@ pub fn impl_window_trait(ast: &DeriveInput) -> TokenStream {
                     #fullscreen_active(self._inner) != 0
                 }
             }
+
+            fn resize_callback(&mut self, cb: Box<dyn FnMut(i32, i32, i32, i32)>) {
+                assert!(!self.was_deleted());
+                unsafe {
+                    unsafe extern "C" fn shim(x: i32, y: i32, w: i32, h: i32, data: *mut raw::c_void) {
+                        let a: *mut Box<dyn FnMut(i32, i32, i32, i32)> = data as *mut Box<dyn FnMut(i32, i32, i32, i32)>;
+                        let f: &mut (dyn FnMut(i32, i32, i32, i32)) = &mut **a;
+                        crate::app::catch_callback_panic(|| f(x, y, w, h));
+                    }
+                    let old_data = #resize_data(self._inner);
+                    if !old_data.is_null() {
+                        let _ = Box::from_raw(old_data as *mut Box<dyn FnMut(i32, i32, i32, i32)>);
+                    }
+                    let a: *mut Box<dyn FnMut(i32, i32, i32, i32)> = Box::into_raw(Box::new(cb));
+                    let data: *mut raw::c_void = a as *mut raw::c_void;
+                    let callback: custom_resize_callback = Some(shim);
+                    #set_resize_callback(self._inner, callback, data);
+                }
+            }
+
+            fn maximize_to_work_area(&mut self) {
+                assert!(!self.was_deleted());
+                let ptr = self._inner as *mut fltk_sys::widget::Fl_Widget;
+                let geometry = (self.x(), self.y(), self.width(), self.height());
+                RESTORE_GEOMETRY.with(|g| {
+                    let mut g = g.borrow_mut();
+                    forget_deleted_windows(&mut g);
+                    if !g.iter().any(|(w, _)| unsafe { w.as_widget_ptr() } == ptr) {
+                        g.push((unsafe { crate::widget::Widget::from_raw(ptr) }, geometry));
+                    }
+                });
+                let (x, y, w, h) = screen_work_area(screen_num(self.x(), self.y()));
+                self.resize(x, y, w, h);
+            }
+
+            fn restore(&mut self) {
+                assert!(!self.was_deleted());
+                let ptr = self._inner as *mut fltk_sys::widget::Fl_Widget;
+                let geometry = RESTORE_GEOMETRY.with(|g| {
+                    let mut g = g.borrow_mut();
+                    forget_deleted_windows(&mut g);
+                    let idx = g.iter().position(|(w, _)| unsafe { w.as_widget_ptr() } == ptr)?;
+                    let (mut w, geometry) = g.remove(idx);
+                    unsafe { w.cleanup() };
+                    Some(geometry)
+                });
+                if let Some((x, y, w, h)) = geometry {
+                    self.resize(x, y, w, h);
+                }
+            }
         }
     };
     gen.into()
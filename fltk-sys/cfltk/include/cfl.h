@@ -22,12 +22,22 @@ int Fl_event(void);
 
 int Fl_event_key(void);
 
+int Fl_event_key_down(int key);
+
 const char *Fl_event_text(void);
 
 int Fl_event_button(void);
 
+int Fl_event_button1(void);
+
+int Fl_event_button2(void);
+
+int Fl_event_button3(void);
+
 int Fl_event_clicks(void);
 
+void Fl_set_event_clicks(int i);
+
 int Fl_event_x(void);
 
 int Fl_event_y(void);
@@ -46,26 +56,79 @@ int Fl_event_is_click(void);
 
 int Fl_event_length(void);
 
+int Fl_compose_state(void);
+
+int Fl_callback_reason(void);
+
+void Fl_set_event(int event, int x, int y, int x_root, int y_root, int keysym, int state,
+                  int clicks, const char *text);
+
+int Fl_handle_widget(Fl_Widget *w, int event);
+
+int Fl_handle_event(int event);
+
+void Fl_event_dispatch(int (*cb)(int event, void *win));
+
 int Fl_event_state(void);
 
 int Fl_screen_h(void);
 
 int Fl_screen_w(void);
 
+int Fl_screen_num(int x, int y);
+
+int Fl_screen_count(void);
+
+float Fl_screen_scale(int n);
+
+void Fl_set_screen_scale(int n, float factor);
+
+void Fl_screen_work_area(int *x, int *y, int *w, int *h, int n);
+
+void Fl_screen_xywh(int *x, int *y, int *w, int *h, int n);
+
 void Fl_paste(Fl_Widget *, int src);
 
-void Fl_set_scheme(const char *scheme);
+void Fl_copy(const char *stuff, int len, int destination);
+
+const char *Fl_clipboard_text(int *len);
+
+int Fl_set_scheme(const char *scheme);
 
 int Fl_scheme(void);
 
+int Fl_reload_scheme(void);
+
+void Fl_background(unsigned char r, unsigned char g, unsigned char b);
+
+void Fl_background2(unsigned char r, unsigned char g, unsigned char b);
+
+void Fl_foreground(unsigned char r, unsigned char g, unsigned char b);
+
 unsigned int Fl_get_rgb_color(unsigned char r, unsigned char g, unsigned char b);
 
+void Fl_get_color_rgb(unsigned int c, unsigned char *r, unsigned char *g, unsigned char *b);
+
 const char *Fl_get_font(int idx);
 
 unsigned char Fl_set_fonts(const char *c);
 
+void Fl_set_font_size(int sz);
+
+int Fl_font_size(void);
+
+void Fl_set_menu_linespacing(int h);
+
+int Fl_menu_linespacing(void);
+
+void Fl_set_box_type_cb(int box, void (*cb)(int, int, int, int, int, unsigned int));
+
 void Fl_add_handler(int (*ev_handler)(int ev));
 
+void Fl_remove_handler(int (*ev_handler)(int ev));
+
+int Fl_test_shortcut(int shortcut);
+
 void Fl_awake_msg(void *msg);
 
 void *Fl_thread_msg(void);
@@ -74,16 +137,44 @@ int Fl_wait(void);
 
 double Fl_wait_for(double);
 
+int Fl_check(void);
+
+int Fl_ready(void);
+
+void Fl_flush(void);
+
+void Fl_set_target_fps(unsigned int fps);
+
 void Fl_add_timeout(double t, void (*)(void *), void *);
 
 void Fl_repeat_timeout(double t, void (*)(void *), void *);
 
 void Fl_remove_timeout(void (*)(void *), void *);
 
+int Fl_has_timeout(void (*)(void *), void *);
+
+void Fl_add_idle(void (*)(void *), void *);
+
+void Fl_remove_idle(void (*)(void *), void *);
+
+int Fl_has_idle(void (*)(void *), void *);
+
+void Fl_add_check(void (*)(void *), void *);
+
+void Fl_remove_check(void (*)(void *), void *);
+
+int Fl_has_check(void (*)(void *), void *);
+
 int Fl_dnd(void);
 
 void *Fl_first_window(void);
 
+void *Fl_grab(void);
+
+void Fl_set_grab(void *win);
+
+void Fl_release(void);
+
 void *Fl_next_window(const void *);
 
 int Fl_should_program_quit(void);
@@ -122,6 +213,14 @@ int Fl_damage(void);
 
 int Fl_visual(int);
 
+int Fl_can_do_visual(int);
+
+void Fl_set_focus_ring(unsigned int color, int dashed);
+
+void Fl_set_overlay_draw(void (*cb)(void *), void *data);
+
+void *Fl_overlay_draw_data(void);
+
 void Fl_own_colormap(void);
 
 Fl_Widget *Fl_pushed(void);
@@ -130,6 +229,8 @@ Fl_Widget *Fl_focus(void);
 
 void Fl_set_focus(void *);
 
+void Fl_set_focus_follows_mouse(int flag);
+
 double Fl_version(void);
 
 int Fl_api_version(void);
@@ -142,10 +243,18 @@ int Fl_abi_version(void);
 
 // void Fl_set_fatal(void (*error)(const char *, ...));
 
-const char *Fl_load_font(const char *path);
+const char *Fl_load_font(const char *path, int index);
 
 void Fl_unload_font(const char *path);
 
+void Fl_reload_font(const char *path);
+
+int Fl_register_global_hotkey(int shortcut, void (*cb)(void *), void *data);
+
+void *Fl_unregister_global_hotkey(int id);
+
+int Fl_capture_screen(unsigned char *buf, int x, int y, int w, int h);
+
 #ifdef __cplusplus
 }
 #endif
@@ -44,6 +44,10 @@ const char *Fl_input(int x, int y, const char *txt, const char *deflt);
 
 const char *Fl_password(int x, int y, const char *txt, const char *deflt);
 
+void Fl_message_title(const char *title);
+
+void Fl_message_title_default(const char *title);
+
 typedef struct Fl_Help_Dialog Fl_Help_Dialog;
 
 Fl_Help_Dialog *Fl_Help_Dialog_new(void);
@@ -51,6 +51,8 @@ WIDGET_DECLARE(Fl_Sys_Menu_Bar)
 
 MENU_DECLARE(Fl_Sys_Menu_Bar)
 
+void Fl_set_menu_font(int font, int size);
+
 Fl_Menu_Item *Fl_Menu_Item_new(char **args, int sz);
 
 void Fl_Menu_Item_delete(Fl_Menu_Item *self);
@@ -25,7 +25,8 @@ extern "C" {
     int widget##_move_down(widget *);                                                              \
     void widget##_show_cursor(widget *, int boolean);                                              \
     void *widget##_set_style_table_entry(widget *self, void *sbuf, unsigned int *color, int *font, \
-                                         int *fontsz, int sz);                                     \
+                                         int *fontsz, unsigned int *attr, unsigned int *bgcolor,   \
+                                         int sz);                                                  \
     void widget##_set_cursor_style(widget *, int style);                                           \
     void widget##_set_cursor_color(widget *, unsigned int color);                                  \
     void widget##_set_scrollbar_width(widget *, int width);                                        \
@@ -58,7 +59,11 @@ extern "C" {
     unsigned int widget##_linenumber_bgcolor(const widget *self);                                  \
     void widget##_set_linenumber_align(widget *self, int val);                                     \
     int widget##_linenumber_align(const widget *self);                                             \
-    int widget##_in_selection(const widget *self, int x, int y);
+    int widget##_in_selection(const widget *self, int x, int y);                                   \
+    Fl_Text_Buffer *widget##_style_buffer(const widget *self);                                     \
+    int widget##_style_table_count(const widget *self);                                            \
+    void widget##_style_table(const widget *self, unsigned int *color, int *font, int *fontsz,     \
+                              unsigned int *attr, unsigned int *bgcolor);
 
 typedef void (*Fl_Text_Modify_Cb)(int pos, int nInserted, int nDeleted, int nRestyled,
                                   const char *deletedText, void *cbArg);
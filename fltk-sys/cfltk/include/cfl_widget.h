@@ -97,8 +97,12 @@ void Fl_Widget_callback_with_captures(Fl_Widget *, Fl_Callback *cb, void *);
         handler inner_handler = NULL;                                                              \
         typedef void (*drawer)(void *data);                                                        \
         drawer inner_drawer = NULL;                                                                \
+        typedef void (*resizer)(int x, int y, int w, int h, void *data);                           \
+        resizer inner_resizer = NULL;                                                              \
+        void *resize_data_ = NULL;                                                                 \
         widget##_Derived(int x, int y, int w, int h, const char *title = 0)                        \
             : widget(x, y, w, h, title) {                                                          \
+            cfl_widget_init(this);                                                                 \
         }                                                                                          \
         operator widget *() {                                                                      \
             return (widget *)this;                                                                 \
@@ -129,9 +133,18 @@ void Fl_Widget_callback_with_captures(Fl_Widget *, Fl_Callback *cb, void *);
             draw_data_ = data;                                                                     \
         }                                                                                          \
         void draw() override {                                                                     \
+            int ring = cfl_focus_ring_begin(this);                                                 \
             widget::draw();                                                                        \
+            if (ring)                                                                              \
+                cfl_focus_ring_end(this);                                                          \
             if (draw_data_ && inner_drawer)                                                        \
                 inner_drawer(draw_data_);                                                          \
+            cfl_overlay_draw(this);                                                                \
+        }                                                                                          \
+        void resize(int x, int y, int w, int h) override {                                         \
+            widget::resize(x, y, w, h);                                                            \
+            if (resize_data_ && inner_resizer)                                                     \
+                inner_resizer(x, y, w, h, resize_data_);                                           \
         }                                                                                          \
     };                                                                                             \
     widget *widget##_new(int x, int y, int width, int height, const char *title) {                 \
@@ -344,4 +357,14 @@ WIDGET_DECLARE(Fl_Widget)
 
 #ifdef __cplusplus
 }
+
+// Per widget type initialization hook, overloaded for the widget types which need it
+inline void cfl_widget_init(void *) {
+}
+
+int cfl_focus_ring_begin(Fl_Widget *w);
+
+void cfl_focus_ring_end(Fl_Widget *w);
+
+void cfl_overlay_draw(Fl_Widget *w);
 #endif
\ No newline at end of file
@@ -12,6 +12,8 @@ typedef union {
     void *opaque;
 } winid;
 
+typedef void (*custom_resize_callback)(int, int, int, int, void *);
+
 #define WINDOW_DECLARE(widget)                                                                     \
     void widget##_make_modal(widget *, unsigned int boolean);                                      \
     void widget##_fullscreen(widget *, unsigned int boolean);                                      \
@@ -27,7 +29,9 @@ typedef union {
     void *widget##_region(const widget *self);                                                     \
     void widget##_set_region(widget *self, void *r);                                               \
     void widget##_iconize(widget *self);                                                           \
-    unsigned int widget##_fullscreen_active(const widget *self);
+    unsigned int widget##_fullscreen_active(const widget *self);                                   \
+    void widget##_set_resize_callback(widget *self, custom_resize_callback cb, void *data);        \
+    void *widget##_resize_data(const widget *self);
 
 WIDGET_DECLARE(Fl_Window)
 
@@ -37,8 +41,16 @@ WINDOW_DECLARE(Fl_Window)
 
 Fl_Window *Fl_Window_find_by_handle(void *handle);
 
+Fl_Window *Fl_Window_current(void);
+
+void Fl_Window_set_default_icon(const void *image);
+
 winid resolve_raw_handle(void *handle);
 
+void Fl_open_display(void);
+
+void Fl_close_display(void);
+
 void *Fl_display(void);
 
 void *Fl_gc(void);
@@ -4,6 +4,7 @@
 #include <windows.h>
 #elif __APPLE__
 #include <ApplicationServices/ApplicationServices.h>
+#include <Carbon/Carbon.h>
 #else /* Assume X11 with XFT/fontconfig - this will break on systems using legacy Xlib fonts */
 #include <fontconfig/fontconfig.h>
 #define USE_XFT 1
@@ -15,10 +16,21 @@
 #include "cfl_new.hpp"
 #include "cfl_widget.h"
 #include <FL/Enumerations.H>
+#include <FL/Fl_Group.H>
 #include <FL/Fl_Widget.H>
+#include <FL/Fl_Window.H>
+#include <FL/fl_draw.H>
+#include <FL/platform.H>
+#ifdef CFLTK_USE_GL
+#include <FL/Fl_Gl_Window.H>
+#endif
+#include <chrono>
+#include <ctype.h>
 #include <stdarg.h>
 #include <stdint.h>
 #include <string.h>
+#include <string>
+#include <vector>
 
 #define STB_TRUETYPE_IMPLEMENTATION
 #include "stb_truetype.h"
@@ -47,6 +59,10 @@ int Fl_event_key(void) {
     return Fl::event_key();
 }
 
+int Fl_event_key_down(int key) {
+    return Fl::event_key(key);
+}
+
 const char *Fl_event_text(void) {
     return Fl::event_text();
 }
@@ -55,10 +71,26 @@ int Fl_event_button(void) {
     return Fl::event_button();
 }
 
+int Fl_event_button1(void) {
+    return Fl::event_button1() != 0;
+}
+
+int Fl_event_button2(void) {
+    return Fl::event_button2() != 0;
+}
+
+int Fl_event_button3(void) {
+    return Fl::event_button3() != 0;
+}
+
 int Fl_event_clicks(void) {
     return Fl::event_clicks();
 }
 
+void Fl_set_event_clicks(int i) {
+    Fl::event_clicks(i);
+}
+
 int Fl_event_x(void) {
     return Fl::event_x();
 }
@@ -94,6 +126,74 @@ int Fl_event_length(void) {
     return Fl::event_length();
 }
 
+int Fl_compose_state(void) {
+    return Fl::compose_state;
+}
+
+int Fl_callback_reason(void) {
+    return Fl::callback_reason();
+}
+
+void Fl_set_event(int event, int x, int y, int x_root, int y_root, int keysym, int state,
+                  int clicks, const char *text) {
+    // The text has to outlive the call, like FLTK's own event text
+    static std::string event_text;
+    event_text = text ? text : "";
+    Fl::e_number = event;
+    Fl::e_x = x;
+    Fl::e_y = y;
+    Fl::e_x_root = x_root;
+    Fl::e_y_root = y_root;
+    Fl::e_keysym = keysym;
+    Fl::e_state = state;
+    Fl::e_clicks = clicks;
+    Fl::e_is_click = clicks > 0;
+    Fl::e_text = (char *)event_text.c_str();
+    Fl::e_length = (int)event_text.size();
+}
+
+int Fl_handle_widget(Fl_Widget *w, int event) {
+    return w->handle(event);
+}
+
+int Fl_handle_event(int event) {
+    return Fl::handle(event, Fl::first_window());
+}
+
+static int (*event_dispatch_cb)(int, void *) = NULL;
+
+static int focus_follows_mouse = 0;
+
+// Single dispatch function shared by Fl_event_dispatch and Fl_set_focus_follows_mouse,
+// so that setting one doesn't replace the other.
+// Events vetoed by the callback are reported as handled so nothing else acts on them.
+// Focus then follows the mouse after move events, whether the callback vetoed them or not
+static int event_dispatch_shim(int event, Fl_Window *w) {
+    int ret = 1;
+    if (!event_dispatch_cb || event_dispatch_cb(event, w))
+        ret = Fl::handle_(event, w);
+    if (focus_follows_mouse && (event == FL_MOVE || event == FL_ENTER)) {
+        Fl_Widget *below = Fl::belowmouse();
+        if (below && below != Fl::focus())
+            below->take_focus();
+    }
+    return ret;
+}
+
+static void update_event_dispatch(void) {
+    Fl::event_dispatch(event_dispatch_cb || focus_follows_mouse ? event_dispatch_shim : NULL);
+}
+
+void Fl_event_dispatch(int (*cb)(int event, void *win)) {
+    event_dispatch_cb = cb;
+    update_event_dispatch();
+}
+
+void Fl_set_focus_follows_mouse(int flag) {
+    focus_follows_mouse = flag;
+    update_event_dispatch();
+}
+
 int Fl_event_state(void) {
     return Fl::event_state();
 }
@@ -106,31 +206,149 @@ int Fl_screen_w(void) {
     return Fl::w();
 }
 
+int Fl_screen_num(int x, int y) {
+    return Fl::screen_num(x, y);
+}
+
+int Fl_screen_count(void) {
+    return Fl::screen_count();
+}
+
+float Fl_screen_scale(int n) {
+    return Fl::screen_scale(n);
+}
+
+void Fl_set_screen_scale(int n, float factor) {
+    Fl::screen_scale(n, factor);
+}
+
+void Fl_screen_work_area(int *x, int *y, int *w, int *h, int n) {
+    Fl::screen_work_area(*x, *y, *w, *h, n);
+}
+
+void Fl_screen_xywh(int *x, int *y, int *w, int *h, int n) {
+    Fl::screen_xywh(*x, *y, *w, *h, n);
+}
+
 void Fl_paste(Fl_Widget *widget, int src) {
     Fl::paste(*widget, src, Fl::clipboard_plain_text);
 }
 
-void Fl_set_scheme(const char *scheme) {
+void Fl_copy(const char *stuff, int len, int destination) {
+    Fl::copy(stuff, len, destination, Fl::clipboard_plain_text);
+}
+
+// Hidden widget receiving the FL_PASTE event of a clipboard request
+struct Clipboard_Receiver : public Fl_Widget {
+    char *text = NULL;
+    int len = 0;
+    int received = 0;
+    Clipboard_Receiver() : Fl_Widget(0, 0, 0, 0, 0) {
+    }
+    int handle(int event) override {
+        if (event != FL_PASTE)
+            return 0;
+        free(text);
+        len = Fl::event_length();
+        text = (char *)malloc(len + 1);
+        if (!text) {
+            len = 0;
+            return 1;
+        }
+        memcpy(text, Fl::event_text(), len);
+        text[len] = 0;
+        received = 1;
+        return 1;
+    }
+    void draw() override {
+    }
+};
+
+const char *Fl_clipboard_text(int *len) {
+    static Clipboard_Receiver *receiver = NULL;
+    *len = 0;
+    if (!Fl::clipboard_contains(Fl::clipboard_plain_text))
+        return NULL;
+    if (!receiver) {
+        Fl_Group *grp = Fl_Group::current();
+        Fl_Group::current(NULL);
+        receiver = new Clipboard_Receiver;
+        Fl_Group::current(grp);
+    }
+    receiver->received = 0;
+    Fl::paste(*receiver, 1, Fl::clipboard_plain_text);
+    // X11 delivers the clipboard content asynchronously, so this reenters the event loop
+    for (int i = 0; i < 100 && !receiver->received; i++)
+        Fl::wait(0.01);
+    if (!receiver->received)
+        return NULL;
+    *len = receiver->len;
+    return receiver->text;
+}
+
+static bool scheme_name_eq(const char *a, const char *b) {
+    for (; *a && *b; a++, b++)
+        if (tolower((unsigned char)*a) != tolower((unsigned char)*b))
+            return false;
+    return *a == *b;
+}
+
+int Fl_set_scheme(const char *scheme) {
+    if (!scheme || !*scheme || scheme_name_eq(scheme, "base") || scheme_name_eq(scheme, "none")) {
+        Fl::scheme(scheme);
+        return 1;
+    }
+    // FLTK falls back to the base scheme for names it doesn't know, so put back the previous one
+    const char *prev = Fl::scheme();
+    char *old = prev ? strdup(prev) : NULL;
     Fl::scheme(scheme);
+    const char *v = Fl::scheme();
+    int ok = v && scheme_name_eq(v, scheme);
+    if (!ok)
+        Fl::scheme(old);
+    free(old);
+    return ok;
 }
 
 int Fl_scheme(void) {
     const char *v = Fl::scheme();
-    if (!strcmp(v, "base")) {
+    if (!v || !strcmp(v, "base")) {
         return 0;
     } else if (!strcmp(v, "gtk+")) {
         return 1;
     } else if (!strcmp(v, "gleam")) {
         return 2;
+    } else if (!strcmp(v, "oxy")) {
+        return 4;
     } else {
         return 3;
     }
 }
 
+int Fl_reload_scheme(void) {
+    return Fl::reload_scheme();
+}
+
+void Fl_background(unsigned char r, unsigned char g, unsigned char b) {
+    Fl::background(r, g, b);
+}
+
+void Fl_background2(unsigned char r, unsigned char g, unsigned char b) {
+    Fl::background2(r, g, b);
+}
+
+void Fl_foreground(unsigned char r, unsigned char g, unsigned char b) {
+    Fl::foreground(r, g, b);
+}
+
 unsigned int Fl_get_rgb_color(unsigned char r, unsigned char g, unsigned char b) {
     return fl_rgb_color(r, g, b);
 }
 
+void Fl_get_color_rgb(unsigned int c, unsigned char *r, unsigned char *g, unsigned char *b) {
+    Fl::get_color(c, *r, *g, *b);
+}
+
 const char *Fl_get_font(int idx) {
     return Fl::get_font(idx);
 }
@@ -139,10 +357,66 @@ unsigned char Fl_set_fonts(const char *c) {
     return Fl::set_fonts(c);
 }
 
+void Fl_set_font_size(int sz) {
+    FL_NORMAL_SIZE = sz;
+}
+
+int Fl_font_size(void) {
+    return FL_NORMAL_SIZE;
+}
+
+void Fl_set_menu_linespacing(int h) {
+    Fl::menu_linespacing(h);
+}
+
+int Fl_menu_linespacing(void) {
+    return Fl::menu_linespacing();
+}
+
+static void (*box_draw_cb)(int, int, int, int, int, unsigned int) = NULL;
+
+// Box draw functions don't receive the box type, so each box type gets its own trampoline
+template <int N>
+static void box_draw_trampoline(int x, int y, int w, int h, Fl_Color c) {
+    if (box_draw_cb)
+        box_draw_cb(N, x, y, w, h, c);
+}
+
+template <int N>
+struct Box_Trampolines {
+    static void fill(Fl_Box_Draw_F **table) {
+        table[N] = box_draw_trampoline<N>;
+        Box_Trampolines<N - 1>::fill(table);
+    }
+};
+
+template <>
+struct Box_Trampolines<-1> {
+    static void fill(Fl_Box_Draw_F **) {
+    }
+};
+
+void Fl_set_box_type_cb(int box, void (*cb)(int, int, int, int, int, unsigned int)) {
+    static Fl_Box_Draw_F *table[256] = {0};
+    if (!table[0])
+        Box_Trampolines<255>::fill(table);
+    box_draw_cb = cb;
+    Fl_Boxtype b = (Fl_Boxtype)(box & 255);
+    Fl::set_boxtype(b, table[b], Fl::box_dx(b), Fl::box_dy(b), Fl::box_dw(b), Fl::box_dh(b));
+}
+
 void Fl_add_handler(int (*ev_handler)(int ev)) {
     Fl::add_handler(ev_handler);
 }
 
+void Fl_remove_handler(int (*ev_handler)(int ev)) {
+    Fl::remove_handler(ev_handler);
+}
+
+int Fl_test_shortcut(int shortcut) {
+    return Fl::test_shortcut(shortcut);
+}
+
 void Fl_awake_msg(void *msg) {
     Fl::awake(msg);
 }
@@ -159,6 +433,65 @@ double Fl_wait_for(double dur) {
     return Fl::wait(dur);
 }
 
+int Fl_check(void) {
+    return Fl::check();
+}
+
+int Fl_ready(void) {
+    return Fl::ready();
+}
+
+void Fl_flush(void) {
+    Fl::flush();
+}
+
+static double frame_interval = 0;
+static int frame_pending = 0;
+static std::chrono::steady_clock::time_point last_frame;
+
+static double frame_elapsed(void) {
+    return std::chrono::duration<double>(std::chrono::steady_clock::now() - last_frame).count();
+}
+
+static void frame_timeout(void *) {
+    frame_pending = 0;
+    Fl::damage(FL_DAMAGE_CHILD);
+}
+
+// Whether a window was just mapped or uncovered, which shouldn't wait for the next frame
+static int frame_exposed(void) {
+    for (Fl_Window *w = Fl::first_window(); w; w = Fl::next_window(w))
+        if (w->damage() & FL_DAMAGE_EXPOSE)
+            return 1;
+    return 0;
+}
+
+// Runs before each flush, holding back the global damage until the next frame is due
+static void frame_check(void *) {
+    if (!Fl::damage())
+        return;
+    double elapsed = frame_elapsed();
+    if (elapsed >= frame_interval || frame_exposed()) {
+        last_frame = std::chrono::steady_clock::now();
+        return;
+    }
+    Fl::damage(0);
+    if (!frame_pending) {
+        frame_pending = 1;
+        Fl::add_timeout(frame_interval - elapsed, frame_timeout);
+    }
+}
+
+void Fl_set_target_fps(unsigned int fps) {
+    Fl::remove_check(frame_check);
+    Fl::remove_timeout(frame_timeout);
+    frame_pending = 0;
+    frame_interval = fps ? 1.0 / fps : 0;
+    if (fps)
+        Fl::add_check(frame_check);
+    Fl::damage(FL_DAMAGE_CHILD);
+}
+
 void Fl_add_timeout(double t, void (*timeout_h)(void *), void *data) {
     Fl::add_timeout(t, timeout_h, data);
 }
@@ -171,6 +504,34 @@ void Fl_remove_timeout(void (*timeout_h)(void *), void *data) {
     Fl::remove_timeout(timeout_h, data);
 }
 
+int Fl_has_timeout(void (*timeout_h)(void *), void *data) {
+    return Fl::has_timeout(timeout_h, data);
+}
+
+void Fl_add_idle(void (*idle_h)(void *), void *data) {
+    Fl::add_idle(idle_h, data);
+}
+
+void Fl_remove_idle(void (*idle_h)(void *), void *data) {
+    Fl::remove_idle(idle_h, data);
+}
+
+int Fl_has_idle(void (*idle_h)(void *), void *data) {
+    return Fl::has_idle(idle_h, data);
+}
+
+void Fl_add_check(void (*check_h)(void *), void *data) {
+    Fl::add_check(check_h, data);
+}
+
+void Fl_remove_check(void (*check_h)(void *), void *data) {
+    Fl::remove_check(check_h, data);
+}
+
+int Fl_has_check(void (*check_h)(void *), void *data) {
+    return Fl::has_check(check_h, data);
+}
+
 int Fl_dnd(void) {
     return Fl::dnd();
 }
@@ -179,6 +540,18 @@ void *Fl_first_window(void) {
     return (void *)Fl::first_window();
 }
 
+void *Fl_grab(void) {
+    return (void *)Fl::grab();
+}
+
+void Fl_set_grab(void *win) {
+    Fl::grab((Fl_Window *)win);
+}
+
+void Fl_release(void) {
+    Fl::release();
+}
+
 void *Fl_next_window(const void *prev) {
     return (void *)Fl::next_window((Fl_Window *)prev);
 }
@@ -277,6 +650,68 @@ int Fl_visual(int mode) {
     return Fl::visual(mode);
 }
 
+int Fl_can_do_visual(int mode) {
+#ifdef CFLTK_USE_GL
+    return Fl_Gl_Window::can_do(mode);
+#else
+    // Without OpenGL, only the basic color and double-buffering modes are available
+    return (mode & ~(FL_RGB | FL_DOUBLE | FL_RGB8)) == 0;
+#endif
+}
+
+static int focus_ring_set = 0;
+static Fl_Color focus_ring_color = FL_BLACK;
+static int focus_ring_dashed = 0;
+
+void Fl_set_focus_ring(unsigned int color, int dashed) {
+    focus_ring_set = 1;
+    focus_ring_color = color;
+    focus_ring_dashed = dashed;
+}
+
+// Disables the default focus box while the focused widget draws itself
+int cfl_focus_ring_begin(Fl_Widget *w) {
+    if (!focus_ring_set || Fl::focus() != w || !Fl::visible_focus() || !w->visible_focus())
+        return 0;
+    Fl::visible_focus(0);
+    return 1;
+}
+
+void cfl_focus_ring_end(Fl_Widget *w) {
+    Fl::visible_focus(1);
+    Fl_Boxtype b = w->box();
+    int X = w->x() + Fl::box_dx(b);
+    int Y = w->y() + Fl::box_dy(b);
+    int W = w->w() - Fl::box_dw(b) - 2;
+    int H = w->h() - Fl::box_dh(b) - 2;
+    fl_color(focus_ring_color);
+    if (focus_ring_dashed)
+        fl_line_style(FL_DASH);
+    fl_rect(X + 1, Y + 1, W, H);
+    fl_line_style(FL_SOLID);
+}
+
+static void (*overlay_draw_cb)(void *) = NULL;
+static void *overlay_draw_data = NULL;
+
+void Fl_set_overlay_draw(void (*cb)(void *), void *data) {
+    overlay_draw_cb = cb;
+    overlay_draw_data = data;
+}
+
+void *Fl_overlay_draw_data(void) {
+    return overlay_draw_data;
+}
+
+// Draws the global overlay on top of top-level windows once they're done drawing
+void cfl_overlay_draw(Fl_Widget *w) {
+    if (!overlay_draw_cb || w->parent() || !w->as_window() || w->as_gl_window())
+        return;
+    fl_push_clip(0, 0, w->w(), w->h());
+    overlay_draw_cb(overlay_draw_data);
+    fl_pop_clip();
+}
+
 void Fl_own_colormap(void) {
     Fl::own_colormap();
 }
@@ -364,7 +799,7 @@ static void v_unload_private_font(const char *pf) {
 
 #endif
 
-const char *Fl_load_font(const char *path) {
+const char *Fl_load_font(const char *path, int index) {
     stbtt_fontinfo font;
     FILE *fptr = fopen(path, "rb");
     if (!fptr)
@@ -396,15 +831,384 @@ const char *Fl_load_font(const char *path) {
                                         STBTT_UNICODE_EID_UNICODE_1_0, STBTT_MAC_EID_ROMAN, 1);
     auto str = (char *)malloc(length + 1);
     snprintf(str, length + 1, "%s", info);
-    auto ret = i_load_private_font(path);
-    auto f = 16;
-    if (ret) {
-        Fl::set_font(f, str);
-    }
     free(buffer);
+    if (!i_load_private_font(path)) {
+        free(str);
+        return nullptr;
+    }
+    // FLTK keeps the name pointer, so str stays alive for as long as the font slot uses it
+    Fl::set_font(index, str);
     return str;
 }
 
 void Fl_unload_font(const char *path) {
     v_unload_private_font(path);
-}
\ No newline at end of file
+}
+
+// Fontconfig can only drop all the app fonts at once, so the ones still loaded are added back after an unload
+void Fl_reload_font(const char *path) {
+#if !defined(_WIN32) && !defined(__APPLE__)
+    FcConfigAppFontAddFile(NULL, (const FcChar8 *)path);
+#else
+    (void)path;
+#endif
+}
+
+struct Fl_Global_Hotkey {
+    int id;
+    int shortcut;
+    void (*cb)(void *);
+    void *data;
+#if defined(_WIN32)
+#elif defined(__APPLE__)
+    EventHotKeyRef ref;
+#elif defined(__unix__)
+    unsigned int keycode;
+    unsigned int mods;
+#endif
+};
+
+static std::vector<Fl_Global_Hotkey> global_hotkeys;
+static int global_hotkey_next_id = 1;
+
+static bool global_hotkey_fire(int id) {
+    for (auto &h : global_hotkeys) {
+        if (h.id == id) {
+            h.cb(h.data);
+            return true;
+        }
+    }
+    return false;
+}
+
+#if defined(_WIN32)
+
+static UINT global_hotkey_vk(int key) {
+    if (key >= 'a' && key <= 'z')
+        return key - 'a' + 'A';
+    if ((key >= 'A' && key <= 'Z') || (key >= '0' && key <= '9'))
+        return key;
+    if (key > FL_F && key <= FL_F + 24)
+        return VK_F1 + (key - FL_F - 1);
+    switch (key) {
+    case ' ': return VK_SPACE;
+    case FL_Escape: return VK_ESCAPE;
+    case FL_Enter: return VK_RETURN;
+    case FL_Tab: return VK_TAB;
+    case FL_BackSpace: return VK_BACK;
+    case FL_Insert: return VK_INSERT;
+    case FL_Delete: return VK_DELETE;
+    case FL_Home: return VK_HOME;
+    case FL_End: return VK_END;
+    case FL_Page_Up: return VK_PRIOR;
+    case FL_Page_Down: return VK_NEXT;
+    case FL_Left: return VK_LEFT;
+    case FL_Up: return VK_UP;
+    case FL_Right: return VK_RIGHT;
+    case FL_Down: return VK_DOWN;
+    case FL_Print: return VK_SNAPSHOT;
+    case FL_Pause: return VK_PAUSE;
+    default: return 0;
+    }
+}
+
+static int global_hotkey_handler(void *event, void *) {
+    MSG *msg = (MSG *)event;
+    if (msg->message != WM_HOTKEY)
+        return 0;
+    return global_hotkey_fire((int)msg->wParam);
+}
+
+static bool global_hotkey_grab(Fl_Global_Hotkey &h) {
+    UINT vk = global_hotkey_vk(h.shortcut & FL_KEY_MASK);
+    if (!vk)
+        return false;
+    UINT mods = 0;
+    if (h.shortcut & FL_SHIFT)
+        mods |= MOD_SHIFT;
+    if (h.shortcut & FL_CTRL)
+        mods |= MOD_CONTROL;
+    if (h.shortcut & FL_ALT)
+        mods |= MOD_ALT;
+    if (h.shortcut & FL_META)
+        mods |= MOD_WIN;
+    // Thread hotkeys post WM_HOTKEY to the queue FLTK pumps, so no window is needed
+    return RegisterHotKey(NULL, h.id, mods, vk) != 0;
+}
+
+static void global_hotkey_ungrab(Fl_Global_Hotkey &h) {
+    UnregisterHotKey(NULL, h.id);
+}
+
+#elif defined(__APPLE__)
+
+static UInt32 global_hotkey_keycode(int key) {
+    // Indexed by the ANSI virtual key code, 0 marks codes that aren't characters
+    static const char ansi[] = "asdfhgzxcv\0bqweryt123465=97-80]ou[ip\0lj'k;\\,/nm.";
+    if (key >= 'A' && key <= 'Z')
+        key = key - 'A' + 'a';
+    if (key > 0 && key < 0x80) {
+        for (UInt32 i = 0; i < sizeof(ansi) - 1; i++)
+            if (ansi[i] == key)
+                return i;
+    }
+    static const UInt32 fkeys[] = {kVK_F1, kVK_F2, kVK_F3,  kVK_F4,  kVK_F5,  kVK_F6,
+                                   kVK_F7, kVK_F8, kVK_F9, kVK_F10, kVK_F11, kVK_F12};
+    if (key > FL_F && key <= FL_F + 12)
+        return fkeys[key - FL_F - 1];
+    switch (key) {
+    case ' ': return kVK_Space;
+    case FL_Escape: return kVK_Escape;
+    case FL_Enter: return kVK_Return;
+    case FL_Tab: return kVK_Tab;
+    case FL_BackSpace: return kVK_Delete;
+    case FL_Delete: return kVK_ForwardDelete;
+    case FL_Home: return kVK_Home;
+    case FL_End: return kVK_End;
+    case FL_Page_Up: return kVK_PageUp;
+    case FL_Page_Down: return kVK_PageDown;
+    case FL_Left: return kVK_LeftArrow;
+    case FL_Up: return kVK_UpArrow;
+    case FL_Right: return kVK_RightArrow;
+    case FL_Down: return kVK_DownArrow;
+    default: return UINT32_MAX;
+    }
+}
+
+static OSStatus global_hotkey_handler(EventHandlerCallRef, EventRef event, void *) {
+    EventHotKeyID hkid;
+    if (GetEventParameter(event, kEventParamDirectObject, typeEventHotKeyID, NULL, sizeof(hkid),
+                          NULL, &hkid) != noErr)
+        return eventNotHandledErr;
+    return global_hotkey_fire((int)hkid.id) ? noErr : eventNotHandledErr;
+}
+
+static bool global_hotkey_grab(Fl_Global_Hotkey &h) {
+    static bool installed = false;
+    if (!installed) {
+        EventTypeSpec spec = {kEventClassKeyboard, kEventHotKeyPressed};
+        if (InstallApplicationEventHandler(&global_hotkey_handler, 1, &spec, NULL, NULL) != noErr)
+            return false;
+        installed = true;
+    }
+    UInt32 keycode = global_hotkey_keycode(h.shortcut & FL_KEY_MASK);
+    if (keycode == UINT32_MAX)
+        return false;
+    UInt32 mods = 0;
+    if (h.shortcut & FL_SHIFT)
+        mods |= shiftKey;
+    if (h.shortcut & FL_CTRL)
+        mods |= controlKey;
+    if (h.shortcut & FL_ALT)
+        mods |= optionKey;
+    if (h.shortcut & FL_META)
+        mods |= cmdKey;
+    EventHotKeyID hkid = {'fltk', (UInt32)h.id};
+    return RegisterEventHotKey(keycode, mods, hkid, GetApplicationEventTarget(), 0, &h.ref) ==
+           noErr;
+}
+
+static void global_hotkey_ungrab(Fl_Global_Hotkey &h) {
+    UnregisterEventHotKey(h.ref);
+}
+
+#elif defined(__unix__)
+
+static const unsigned int global_hotkey_mod_mask = ShiftMask | ControlMask | Mod1Mask | Mod4Mask;
+
+// Grabs are per modifier state, so each hotkey is grabbed again with CapsLock and NumLock held
+static const unsigned int global_hotkey_lock_masks[] = {0, LockMask, Mod2Mask, LockMask | Mod2Mask};
+
+static bool global_hotkey_x_error = false;
+
+static int global_hotkey_x_error_handler(Display *, XErrorEvent *e) {
+    if (e->error_code == BadAccess)
+        global_hotkey_x_error = true;
+    return 0;
+}
+
+static int global_hotkey_handler(void *event, void *) {
+    XEvent *xev = (XEvent *)event;
+    if (xev->type != KeyPress || xev->xkey.window != RootWindow(fl_display, fl_screen))
+        return 0;
+    for (auto &h : global_hotkeys) {
+        if (h.keycode == xev->xkey.keycode && h.mods == (xev->xkey.state & global_hotkey_mod_mask))
+            return global_hotkey_fire(h.id);
+    }
+    return 0;
+}
+
+static void global_hotkey_ungrab(Fl_Global_Hotkey &h) {
+    Window root = RootWindow(fl_display, fl_screen);
+    for (auto lock : global_hotkey_lock_masks)
+        XUngrabKey(fl_display, h.keycode, h.mods | lock, root);
+    XFlush(fl_display);
+}
+
+static bool global_hotkey_grab(Fl_Global_Hotkey &h) {
+    fl_open_display();
+    // FLTK key codes are X keysyms
+    h.keycode = XKeysymToKeycode(fl_display, h.shortcut & FL_KEY_MASK);
+    if (!h.keycode)
+        return false;
+    h.mods = 0;
+    if (h.shortcut & FL_SHIFT)
+        h.mods |= ShiftMask;
+    if (h.shortcut & FL_CTRL)
+        h.mods |= ControlMask;
+    if (h.shortcut & FL_ALT)
+        h.mods |= Mod1Mask;
+    if (h.shortcut & FL_META)
+        h.mods |= Mod4Mask;
+    Window root = RootWindow(fl_display, fl_screen);
+    // A key already grabbed by another client fails asynchronously with BadAccess
+    XSync(fl_display, False);
+    global_hotkey_x_error = false;
+    auto old_handler = XSetErrorHandler(global_hotkey_x_error_handler);
+    for (auto lock : global_hotkey_lock_masks)
+        XGrabKey(fl_display, h.keycode, h.mods | lock, root, True, GrabModeAsync, GrabModeAsync);
+    XSync(fl_display, False);
+    XSetErrorHandler(old_handler);
+    if (global_hotkey_x_error) {
+        global_hotkey_ungrab(h);
+        return false;
+    }
+    return true;
+}
+
+#endif
+
+int Fl_register_global_hotkey(int shortcut, void (*cb)(void *), void *data) {
+#if defined(_WIN32) || defined(__APPLE__) || defined(__unix__)
+    Fl_Global_Hotkey h;
+    h.id = global_hotkey_next_id;
+    h.shortcut = shortcut;
+    h.cb = cb;
+    h.data = data;
+    if (!global_hotkey_grab(h))
+        return 0;
+#if !defined(__APPLE__)
+    static bool installed = false;
+    if (!installed) {
+        Fl::add_system_handler(global_hotkey_handler, NULL);
+        installed = true;
+    }
+#endif
+    global_hotkey_next_id++;
+    global_hotkeys.push_back(h);
+    return h.id;
+#else
+    (void)shortcut;
+    (void)cb;
+    (void)data;
+    return -1;
+#endif
+}
+
+void *Fl_unregister_global_hotkey(int id) {
+    for (auto it = global_hotkeys.begin(); it != global_hotkeys.end(); ++it) {
+        if (it->id == id) {
+#if defined(_WIN32) || defined(__APPLE__) || defined(__unix__)
+            global_hotkey_ungrab(*it);
+#endif
+            auto data = it->data;
+            global_hotkeys.erase(it);
+            return data;
+        }
+    }
+    return NULL;
+}
+
+int Fl_capture_screen(unsigned char *buf, int x, int y, int w, int h) {
+    if (!buf || w <= 0 || h <= 0)
+        return 0;
+#if defined(_WIN32)
+    HDC screen = GetDC(NULL);
+    if (!screen)
+        return 0;
+    HDC mem = CreateCompatibleDC(screen);
+    HBITMAP bmp = CreateCompatibleBitmap(screen, w, h);
+    HGDIOBJ old = SelectObject(mem, bmp);
+    int ret = BitBlt(mem, 0, 0, w, h, screen, x, y, SRCCOPY);
+    SelectObject(mem, old);
+    if (ret) {
+        BITMAPINFO bi;
+        memset(&bi, 0, sizeof(bi));
+        bi.bmiHeader.biSize = sizeof(BITMAPINFOHEADER);
+        bi.bmiHeader.biWidth = w;
+        bi.bmiHeader.biHeight = -h; // top-down rows
+        bi.bmiHeader.biPlanes = 1;
+        bi.bmiHeader.biBitCount = 32;
+        bi.bmiHeader.biCompression = BI_RGB;
+        std::vector<unsigned char> bgra((size_t)w * h * 4);
+        ret = GetDIBits(mem, bmp, 0, h, bgra.data(), &bi, DIB_RGB_COLORS) == h;
+        for (size_t i = 0; ret && i < (size_t)w * h; i++) {
+            buf[i * 3] = bgra[i * 4 + 2];
+            buf[i * 3 + 1] = bgra[i * 4 + 1];
+            buf[i * 3 + 2] = bgra[i * 4];
+        }
+    }
+    DeleteObject(bmp);
+    DeleteDC(mem);
+    ReleaseDC(NULL, screen);
+    return ret;
+#elif defined(__APPLE__)
+    CGImageRef img = CGWindowListCreateImage(CGRectMake(x, y, w, h),
+                                             kCGWindowListOptionOnScreenOnly, kCGNullWindowID,
+                                             kCGWindowImageNominalResolution);
+    if (!img)
+        return 0;
+    std::vector<unsigned char> rgba((size_t)w * h * 4);
+    CGColorSpaceRef cs = CGColorSpaceCreateDeviceRGB();
+    CGContextRef ctx = CGBitmapContextCreate(rgba.data(), w, h, 8, w * 4, cs,
+                                             kCGImageAlphaNoneSkipLast);
+    CGColorSpaceRelease(cs);
+    if (!ctx) {
+        CGImageRelease(img);
+        return 0;
+    }
+    CGContextDrawImage(ctx, CGRectMake(0, 0, w, h), img);
+    CGContextRelease(ctx);
+    CGImageRelease(img);
+    for (size_t i = 0; i < (size_t)w * h; i++) {
+        buf[i * 3] = rgba[i * 4];
+        buf[i * 3 + 1] = rgba[i * 4 + 1];
+        buf[i * 3 + 2] = rgba[i * 4 + 2];
+    }
+    return 1;
+#elif defined(__unix__)
+    fl_open_display();
+    XImage *img = XGetImage(fl_display, RootWindow(fl_display, fl_screen), x, y, w, h, AllPlanes,
+                            ZPixmap);
+    if (!img)
+        return 0;
+    int ret = fl_visual->c_class == TrueColor;
+    if (ret) {
+        // Shift each channel mask down to its low bit and scale it to 8 bits
+        unsigned long masks[3] = {img->red_mask, img->green_mask, img->blue_mask};
+        int shifts[3], maxes[3];
+        for (int c = 0; c < 3; c++) {
+            shifts[c] = 0;
+            while (masks[c] && !((masks[c] >> shifts[c]) & 1))
+                shifts[c]++;
+            maxes[c] = (int)(masks[c] >> shifts[c]);
+            if (!maxes[c])
+                ret = 0;
+        }
+        for (int j = 0; ret && j < h; j++) {
+            for (int i = 0; i < w; i++) {
+                unsigned long px = XGetPixel(img, i, j);
+                unsigned char *dst = buf + ((size_t)j * w + i) * 3;
+                for (int c = 0; c < 3; c++)
+                    dst[c] = (unsigned char)(((px & masks[c]) >> shifts[c]) * 255 / maxes[c]);
+            }
+        }
+    }
+    XDestroyImage(img);
+    return ret;
+#else
+    (void)x;
+    (void)y;
+    return 0;
+#endif
+}
@@ -111,6 +111,14 @@ const char *Fl_password(int x, int y, const char *txt, const char *deflt) {
     return fl_password("%s", deflt, txt);
 }
 
+void Fl_message_title(const char *title) {
+    fl_message_title(title);
+}
+
+void Fl_message_title_default(const char *title) {
+    fl_message_title_default(title);
+}
+
 Fl_Help_Dialog *Fl_Help_Dialog_new(void) {
     return new Fl_Help_Dialog();
 }
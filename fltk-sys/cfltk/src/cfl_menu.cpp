@@ -9,6 +9,25 @@
 #include <FL/Fl_Menu_Item.H>
  #include "cfl_new.hpp"
 
+static int menu_font = -1;
+static int menu_size = -1;
+
+void Fl_set_menu_font(int font, int size) {
+    menu_font = font;
+    menu_size = size;
+}
+
+// Applies the global menu font to newly created menus.
+// The derived widget constructors call cfl_widget_init(this), and for menus the derived-to-base
+// conversion to Fl_Menu_ * ranks above the conversion to void *, so overload resolution picks this
+// overload over the no-op one in cfl_widget.h. Other widgets only match the void * overload
+void cfl_widget_init(Fl_Menu_ *self) {
+    if (menu_font >= 0)
+        self->textfont(menu_font);
+    if (menu_size >= 0)
+        self->textsize(menu_size);
+}
+
 #define MENU_DEFINE(widget)                                                                        \
     void widget##_add(widget *self, const char *name, int shortcut, Fl_Callback *cb, void *data,   \
                       int flag) {                                                                  \
@@ -71,12 +71,13 @@
         LOCK(if (boolean) self->show_cursor(); else self->hide_cursor();)                          \
     }                                                                                              \
     void *widget##_set_style_table_entry(widget *self, void *sbuff, unsigned int *color,           \
-                                         int *font, int *fontsz, int sz) {                         \
+                                         int *font, int *fontsz, unsigned int *attr,               \
+                                         unsigned int *bgcolor, int sz) {                          \
         Fl_Text_Display::Style_Table_Entry *stable = new Fl_Text_Display::Style_Table_Entry[sz];   \
         if (!stable)                                                                               \
             return NULL;                                                                           \
         for (int i = 0; i < sz; ++i) {                                                             \
-            stable[i] = {color[i], font[i], fontsz[i]};                                            \
+            stable[i] = {color[i], font[i], fontsz[i], attr[i], bgcolor[i]};                       \
         }                                                                                          \
         LOCK(self->highlight_data((Fl_Text_Buffer *)sbuff, stable, sz, 'A', 0, 0);)                \
         return (void *)stable;                                                                     \
@@ -183,7 +184,34 @@
     }                                                                                              \
     int widget##_in_selection(const widget *self, int x, int y) {                                  \
         return self->in_selection(x, y);                                                           \
+    }                                                                                              \
+    Fl_Text_Buffer *widget##_style_buffer(const widget *self) {                                    \
+        return self->style_buffer();                                                               \
+    }                                                                                              \
+    int widget##_style_table_count(const widget *self) {                                           \
+        return Style_Table_Access::count(self);                                                    \
+    }                                                                                              \
+    void widget##_style_table(const widget *self, unsigned int *color, int *font, int *fontsz,     \
+                              unsigned int *attr, unsigned int *bgcolor) {                         \
+        const Fl_Text_Display::Style_Table_Entry *stable = Style_Table_Access::table(self);        \
+        for (int i = 0; i < Style_Table_Access::count(self); ++i) {                                \
+            color[i] = stable[i].color;                                                            \
+            font[i] = stable[i].font;                                                              \
+            fontsz[i] = stable[i].size;                                                            \
+            attr[i] = stable[i].attr;                                                              \
+            bgcolor[i] = stable[i].bgcolor;                                                        \
+        }                                                                                          \
+    }
+
+// Reads the style table set with highlight_data, which Fl_Text_Display keeps protected
+struct Style_Table_Access : public Fl_Text_Display {
+    static int count(const Fl_Text_Display *self) {
+        return ((const Style_Table_Access *)self)->mNStyles;
+    }
+    static const Style_Table_Entry *table(const Fl_Text_Display *self) {
+        return ((const Style_Table_Access *)self)->mStyleTable;
     }
+};
 
 Fl_Text_Buffer *Fl_Text_Buffer_new(void) {
     return new Fl_Text_Buffer;
@@ -67,6 +67,13 @@
     }                                                                                              \
     unsigned int widget##_fullscreen_active(const widget *self) {                                  \
         return self->fullscreen_active();                                                          \
+    }                                                                                              \
+    void widget##_set_resize_callback(widget *self, custom_resize_callback cb, void *data) {       \
+        LOCK(widget##_Derived *d = (widget##_Derived *)self; d->inner_resizer = cb;                \
+             d->resize_data_ = data;)                                                              \
+    }                                                                                              \
+    void *widget##_resize_data(const widget *self) {                                               \
+        return ((const widget##_Derived *)self)->resize_data_;                                     \
     }
 
 WIDGET_DEFINE(Fl_Window)
@@ -79,6 +86,14 @@ Fl_Window *Fl_Window_find_by_handle(void *handle) {
     return fl_find(*(Window *)handle);
 }
 
+Fl_Window *Fl_Window_current(void) {
+    return Fl_Window::current();
+}
+
+void Fl_Window_set_default_icon(const void *image) {
+    Fl_Window::default_icon((const Fl_RGB_Image *)((Fl_Image *)image));
+}
+
 winid resolve_raw_handle(void *handle) {
     winid w;
 #if defined(_WIN32) || defined(__APPLE__) || defined(__ANDROID__)
@@ -90,6 +105,14 @@ winid resolve_raw_handle(void *handle) {
     return w;
 }
 
+void Fl_open_display(void) {
+    fl_open_display();
+}
+
+void Fl_close_display(void) {
+    fl_close_display();
+}
+
 void *Fl_display(void) {
 #ifdef __APPLE__
     return 0;
@@ -98,6 +98,12 @@ extern "C" {
         deflt: *const libc::c_char,
     ) -> *const libc::c_char;
 }
+extern "C" {
+    pub fn Fl_message_title(title: *const libc::c_char);
+}
+extern "C" {
+    pub fn Fl_message_title_default(title: *const libc::c_char);
+}
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct Fl_Help_Dialog {
@@ -29,15 +29,30 @@ extern "C" {
 extern "C" {
     pub fn Fl_event_key() -> libc::c_int;
 }
+extern "C" {
+    pub fn Fl_event_key_down(key: libc::c_int) -> libc::c_int;
+}
 extern "C" {
     pub fn Fl_event_text() -> *const libc::c_char;
 }
 extern "C" {
     pub fn Fl_event_button() -> libc::c_int;
 }
+extern "C" {
+    pub fn Fl_event_button1() -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_event_button2() -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_event_button3() -> libc::c_int;
+}
 extern "C" {
     pub fn Fl_event_clicks() -> libc::c_int;
 }
+extern "C" {
+    pub fn Fl_set_event_clicks(i: libc::c_int);
+}
 extern "C" {
     pub fn Fl_event_x() -> libc::c_int;
 }
@@ -65,6 +80,38 @@ extern "C" {
 extern "C" {
     pub fn Fl_event_length() -> libc::c_int;
 }
+extern "C" {
+    pub fn Fl_compose_state() -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_callback_reason() -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_set_event(
+        event: libc::c_int,
+        x: libc::c_int,
+        y: libc::c_int,
+        x_root: libc::c_int,
+        y_root: libc::c_int,
+        keysym: libc::c_int,
+        state: libc::c_int,
+        clicks: libc::c_int,
+        text: *const libc::c_char,
+    );
+}
+extern "C" {
+    pub fn Fl_handle_widget(w: *mut Fl_Widget, event: libc::c_int) -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_handle_event(event: libc::c_int) -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_event_dispatch(
+        cb: ::core::option::Option<
+            unsafe extern "C" fn(event: libc::c_int, win: *mut libc::c_void) -> libc::c_int,
+        >,
+    );
+}
 extern "C" {
     pub fn Fl_event_state() -> libc::c_int;
 }
@@ -74,29 +121,120 @@ extern "C" {
 extern "C" {
     pub fn Fl_screen_w() -> libc::c_int;
 }
+extern "C" {
+    pub fn Fl_screen_num(x: libc::c_int, y: libc::c_int) -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_screen_count() -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_screen_scale(n: libc::c_int) -> f32;
+}
+extern "C" {
+    pub fn Fl_set_screen_scale(n: libc::c_int, factor: f32);
+}
+extern "C" {
+    pub fn Fl_screen_work_area(
+        x: *mut libc::c_int,
+        y: *mut libc::c_int,
+        w: *mut libc::c_int,
+        h: *mut libc::c_int,
+        n: libc::c_int,
+    );
+}
+extern "C" {
+    pub fn Fl_screen_xywh(
+        x: *mut libc::c_int,
+        y: *mut libc::c_int,
+        w: *mut libc::c_int,
+        h: *mut libc::c_int,
+        n: libc::c_int,
+    );
+}
 extern "C" {
     pub fn Fl_paste(arg1: *mut Fl_Widget, src: libc::c_int);
 }
 extern "C" {
-    pub fn Fl_set_scheme(scheme: *const libc::c_char);
+    pub fn Fl_copy(stuff: *const libc::c_char, len: libc::c_int, destination: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_clipboard_text(len: *mut libc::c_int) -> *const libc::c_char;
+}
+extern "C" {
+    pub fn Fl_set_scheme(scheme: *const libc::c_char) -> libc::c_int;
 }
 extern "C" {
     pub fn Fl_scheme() -> libc::c_int;
 }
+extern "C" {
+    pub fn Fl_reload_scheme() -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_background(r: libc::c_uchar, g: libc::c_uchar, b: libc::c_uchar);
+}
+extern "C" {
+    pub fn Fl_background2(r: libc::c_uchar, g: libc::c_uchar, b: libc::c_uchar);
+}
+extern "C" {
+    pub fn Fl_foreground(r: libc::c_uchar, g: libc::c_uchar, b: libc::c_uchar);
+}
 extern "C" {
     pub fn Fl_get_rgb_color(r: libc::c_uchar, g: libc::c_uchar, b: libc::c_uchar) -> libc::c_uint;
 }
+extern "C" {
+    pub fn Fl_get_color_rgb(
+        c: libc::c_uint,
+        r: *mut libc::c_uchar,
+        g: *mut libc::c_uchar,
+        b: *mut libc::c_uchar,
+    );
+}
 extern "C" {
     pub fn Fl_get_font(idx: libc::c_int) -> *const libc::c_char;
 }
 extern "C" {
     pub fn Fl_set_fonts(c: *const libc::c_char) -> libc::c_uchar;
 }
+extern "C" {
+    pub fn Fl_set_font_size(sz: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_font_size() -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_set_menu_linespacing(h: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_menu_linespacing() -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_set_box_type_cb(
+        box_: libc::c_int,
+        cb: ::core::option::Option<
+            unsafe extern "C" fn(
+                box_: libc::c_int,
+                x: libc::c_int,
+                y: libc::c_int,
+                w: libc::c_int,
+                h: libc::c_int,
+                c: libc::c_uint,
+            ),
+        >,
+    );
+}
 extern "C" {
     pub fn Fl_add_handler(
         ev_handler: ::core::option::Option<unsafe extern "C" fn(ev: libc::c_int) -> libc::c_int>,
     );
 }
+extern "C" {
+    pub fn Fl_remove_handler(
+        ev_handler: ::core::option::Option<unsafe extern "C" fn(ev: libc::c_int) -> libc::c_int>,
+    );
+}
+extern "C" {
+    pub fn Fl_test_shortcut(shortcut: libc::c_int) -> libc::c_int;
+}
 extern "C" {
     pub fn Fl_awake_msg(msg: *mut libc::c_void);
 }
@@ -109,6 +247,18 @@ extern "C" {
 extern "C" {
     pub fn Fl_wait_for(arg1: f64) -> f64;
 }
+extern "C" {
+    pub fn Fl_check() -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_ready() -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_flush();
+}
+extern "C" {
+    pub fn Fl_set_target_fps(fps: libc::c_uint);
+}
 extern "C" {
     pub fn Fl_add_timeout(
         t: f64,
@@ -129,12 +279,63 @@ extern "C" {
         arg2: *mut libc::c_void,
     );
 }
+extern "C" {
+    pub fn Fl_has_timeout(
+        arg1: ::core::option::Option<unsafe extern "C" fn(arg1: *mut libc::c_void)>,
+        arg2: *mut libc::c_void,
+    ) -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_add_idle(
+        arg1: ::core::option::Option<unsafe extern "C" fn(arg1: *mut libc::c_void)>,
+        arg2: *mut libc::c_void,
+    );
+}
+extern "C" {
+    pub fn Fl_remove_idle(
+        arg1: ::core::option::Option<unsafe extern "C" fn(arg1: *mut libc::c_void)>,
+        arg2: *mut libc::c_void,
+    );
+}
+extern "C" {
+    pub fn Fl_has_idle(
+        arg1: ::core::option::Option<unsafe extern "C" fn(arg1: *mut libc::c_void)>,
+        arg2: *mut libc::c_void,
+    ) -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_add_check(
+        arg1: ::core::option::Option<unsafe extern "C" fn(arg1: *mut libc::c_void)>,
+        arg2: *mut libc::c_void,
+    );
+}
+extern "C" {
+    pub fn Fl_remove_check(
+        arg1: ::core::option::Option<unsafe extern "C" fn(arg1: *mut libc::c_void)>,
+        arg2: *mut libc::c_void,
+    );
+}
+extern "C" {
+    pub fn Fl_has_check(
+        arg1: ::core::option::Option<unsafe extern "C" fn(arg1: *mut libc::c_void)>,
+        arg2: *mut libc::c_void,
+    ) -> libc::c_int;
+}
 extern "C" {
     pub fn Fl_dnd() -> libc::c_int;
 }
 extern "C" {
     pub fn Fl_first_window() -> *mut libc::c_void;
 }
+extern "C" {
+    pub fn Fl_grab() -> *mut libc::c_void;
+}
+extern "C" {
+    pub fn Fl_set_grab(win: *mut libc::c_void);
+}
+extern "C" {
+    pub fn Fl_release();
+}
 extern "C" {
     pub fn Fl_next_window(arg1: *const libc::c_void) -> *mut libc::c_void;
 }
@@ -194,6 +395,21 @@ extern "C" {
 extern "C" {
     pub fn Fl_visual(arg1: libc::c_int) -> libc::c_int;
 }
+extern "C" {
+    pub fn Fl_can_do_visual(arg1: libc::c_int) -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_set_focus_ring(color: libc::c_uint, dashed: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_set_overlay_draw(
+        cb: ::core::option::Option<unsafe extern "C" fn(arg1: *mut libc::c_void)>,
+        data: *mut libc::c_void,
+    );
+}
+extern "C" {
+    pub fn Fl_overlay_draw_data() -> *mut libc::c_void;
+}
 extern "C" {
     pub fn Fl_own_colormap();
 }
@@ -203,6 +419,9 @@ extern "C" {
 extern "C" {
     pub fn Fl_focus() -> *mut Fl_Widget;
 }
+extern "C" {
+    pub fn Fl_set_focus_follows_mouse(flag: libc::c_int);
+}
 extern "C" {
     pub fn Fl_set_focus(arg1: *mut libc::c_void);
 }
@@ -216,8 +435,30 @@ extern "C" {
     pub fn Fl_abi_version() -> libc::c_int;
 }
 extern "C" {
-    pub fn Fl_load_font(path: *const libc::c_char) -> *const libc::c_char;
+    pub fn Fl_load_font(path: *const libc::c_char, index: libc::c_int) -> *const libc::c_char;
 }
 extern "C" {
     pub fn Fl_unload_font(path: *const libc::c_char);
 }
+extern "C" {
+    pub fn Fl_reload_font(path: *const libc::c_char);
+}
+extern "C" {
+    pub fn Fl_register_global_hotkey(
+        shortcut: libc::c_int,
+        cb: ::core::option::Option<unsafe extern "C" fn(arg1: *mut libc::c_void)>,
+        data: *mut libc::c_void,
+    ) -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_unregister_global_hotkey(id: libc::c_int) -> *mut libc::c_void;
+}
+extern "C" {
+    pub fn Fl_capture_screen(
+        buf: *mut libc::c_uchar,
+        x: libc::c_int,
+        y: libc::c_int,
+        w: libc::c_int,
+        h: libc::c_int,
+    ) -> libc::c_int;
+}
@@ -1544,6 +1544,9 @@ extern "C" {
 extern "C" {
     pub fn Fl_Sys_Menu_Bar_remove(self_: *mut Fl_Sys_Menu_Bar, idx: libc::c_int);
 }
+extern "C" {
+    pub fn Fl_set_menu_font(font: libc::c_int, size: libc::c_int);
+}
 extern "C" {
     pub fn Fl_Menu_Item_new(args: *mut *mut libc::c_char, sz: libc::c_int) -> *mut Fl_Menu_Item;
 }
@@ -730,6 +730,8 @@ extern "C" {
         color: *mut libc::c_uint,
         font: *mut libc::c_int,
         fontsz: *mut libc::c_int,
+        attr: *mut libc::c_uint,
+        bgcolor: *mut libc::c_uint,
         sz: libc::c_int,
     ) -> *mut libc::c_void;
 }
@@ -856,6 +858,22 @@ extern "C" {
         y: libc::c_int,
     ) -> libc::c_int;
 }
+extern "C" {
+    pub fn Fl_Text_Display_style_buffer(self_: *const Fl_Text_Display) -> *mut Fl_Text_Buffer;
+}
+extern "C" {
+    pub fn Fl_Text_Display_style_table_count(self_: *const Fl_Text_Display) -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_Text_Display_style_table(
+        self_: *const Fl_Text_Display,
+        color: *mut libc::c_uint,
+        font: *mut libc::c_int,
+        fontsz: *mut libc::c_int,
+        attr: *mut libc::c_uint,
+        bgcolor: *mut libc::c_uint,
+    );
+}
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct Fl_Text_Editor {
@@ -1166,6 +1184,8 @@ extern "C" {
         color: *mut libc::c_uint,
         font: *mut libc::c_int,
         fontsz: *mut libc::c_int,
+        attr: *mut libc::c_uint,
+        bgcolor: *mut libc::c_uint,
         sz: libc::c_int,
     ) -> *mut libc::c_void;
 }
@@ -1287,6 +1307,22 @@ extern "C" {
         y: libc::c_int,
     ) -> libc::c_int;
 }
+extern "C" {
+    pub fn Fl_Text_Editor_style_buffer(self_: *const Fl_Text_Editor) -> *mut Fl_Text_Buffer;
+}
+extern "C" {
+    pub fn Fl_Text_Editor_style_table_count(self_: *const Fl_Text_Editor) -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_Text_Editor_style_table(
+        self_: *const Fl_Text_Editor,
+        color: *mut libc::c_uint,
+        font: *mut libc::c_int,
+        fontsz: *mut libc::c_int,
+        attr: *mut libc::c_uint,
+        bgcolor: *mut libc::c_uint,
+    );
+}
 extern "C" {
     pub fn Fl_Text_Editor_kf_copy(e: *mut Fl_Text_Editor) -> libc::c_int;
 }
@@ -1733,6 +1769,8 @@ extern "C" {
         color: *mut libc::c_uint,
         font: *mut libc::c_int,
         fontsz: *mut libc::c_int,
+        attr: *mut libc::c_uint,
+        bgcolor: *mut libc::c_uint,
         sz: libc::c_int,
     ) -> *mut libc::c_void;
 }
@@ -1882,6 +1920,22 @@ extern "C" {
         y: libc::c_int,
     ) -> libc::c_int;
 }
+extern "C" {
+    pub fn Fl_Simple_Terminal_style_buffer(self_: *const Fl_Simple_Terminal) -> *mut Fl_Text_Buffer;
+}
+extern "C" {
+    pub fn Fl_Simple_Terminal_style_table_count(self_: *const Fl_Simple_Terminal) -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_Simple_Terminal_style_table(
+        self_: *const Fl_Simple_Terminal,
+        color: *mut libc::c_uint,
+        font: *mut libc::c_int,
+        fontsz: *mut libc::c_int,
+        attr: *mut libc::c_uint,
+        bgcolor: *mut libc::c_uint,
+    );
+}
 extern "C" {
     pub fn Fl_delete_stable(arg1: *mut libc::c_void);
 }
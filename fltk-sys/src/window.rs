@@ -2148,6 +2148,15 @@ fn bindgen_test_layout_winid() {
         )
     );
 }
+pub type custom_resize_callback = ::core::option::Option<
+    unsafe extern "C" fn(
+        arg1: libc::c_int,
+        arg2: libc::c_int,
+        arg3: libc::c_int,
+        arg4: libc::c_int,
+        arg5: *mut libc::c_void,
+    ),
+>;
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct Fl_Window {
@@ -2449,12 +2458,34 @@ extern "C" {
 extern "C" {
     pub fn Fl_Window_fullscreen_active(self_: *const Fl_Window) -> libc::c_uint;
 }
+extern "C" {
+    pub fn Fl_Window_set_resize_callback(
+        self_: *mut Fl_Window,
+        cb: custom_resize_callback,
+        data: *mut libc::c_void,
+    );
+}
+extern "C" {
+    pub fn Fl_Window_resize_data(self_: *const Fl_Window) -> *mut libc::c_void;
+}
 extern "C" {
     pub fn Fl_Window_find_by_handle(handle: *mut libc::c_void) -> *mut Fl_Window;
 }
+extern "C" {
+    pub fn Fl_Window_current() -> *mut Fl_Window;
+}
+extern "C" {
+    pub fn Fl_Window_set_default_icon(image: *const libc::c_void);
+}
 extern "C" {
     pub fn resolve_raw_handle(handle: *mut libc::c_void) -> winid;
 }
+extern "C" {
+    pub fn Fl_open_display();
+}
+extern "C" {
+    pub fn Fl_close_display();
+}
 extern "C" {
     pub fn Fl_display() -> *mut libc::c_void;
 }
@@ -2785,6 +2816,16 @@ extern "C" {
 extern "C" {
     pub fn Fl_Single_Window_fullscreen_active(self_: *const Fl_Single_Window) -> libc::c_uint;
 }
+extern "C" {
+    pub fn Fl_Single_Window_set_resize_callback(
+        self_: *mut Fl_Single_Window,
+        cb: custom_resize_callback,
+        data: *mut libc::c_void,
+    );
+}
+extern "C" {
+    pub fn Fl_Single_Window_resize_data(self_: *const Fl_Single_Window) -> *mut libc::c_void;
+}
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct Fl_Double_Window {
@@ -3102,6 +3143,16 @@ extern "C" {
 extern "C" {
     pub fn Fl_Double_Window_fullscreen_active(self_: *const Fl_Double_Window) -> libc::c_uint;
 }
+extern "C" {
+    pub fn Fl_Double_Window_set_resize_callback(
+        self_: *mut Fl_Double_Window,
+        cb: custom_resize_callback,
+        data: *mut libc::c_void,
+    );
+}
+extern "C" {
+    pub fn Fl_Double_Window_resize_data(self_: *const Fl_Double_Window) -> *mut libc::c_void;
+}
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct Fl_Menu_Window {
@@ -3413,6 +3464,16 @@ extern "C" {
 extern "C" {
     pub fn Fl_Menu_Window_fullscreen_active(self_: *const Fl_Menu_Window) -> libc::c_uint;
 }
+extern "C" {
+    pub fn Fl_Menu_Window_set_resize_callback(
+        self_: *mut Fl_Menu_Window,
+        cb: custom_resize_callback,
+        data: *mut libc::c_void,
+    );
+}
+extern "C" {
+    pub fn Fl_Menu_Window_resize_data(self_: *const Fl_Menu_Window) -> *mut libc::c_void;
+}
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct Fl_Gl_Window {
@@ -3714,6 +3775,16 @@ extern "C" {
 extern "C" {
     pub fn Fl_Gl_Window_fullscreen_active(self_: *const Fl_Gl_Window) -> libc::c_uint;
 }
+extern "C" {
+    pub fn Fl_Gl_Window_set_resize_callback(
+        self_: *mut Fl_Gl_Window,
+        cb: custom_resize_callback,
+        data: *mut libc::c_void,
+    );
+}
+extern "C" {
+    pub fn Fl_Gl_Window_resize_data(self_: *const Fl_Gl_Window) -> *mut libc::c_void;
+}
 extern "C" {
     pub fn Fl_Gl_Window_flush(self_: *mut Fl_Gl_Window);
 }
@@ -0,0 +1,28 @@
+use fltk::{app::*, frame::*, window::*};
+
+fn main() {
+    let app = App::default();
+    let mut wind = Window::new(100, 100, 400, 300, "Clicks");
+    let mut frame = Frame::new(0, 0, 400, 300, "Click, double click or triple click");
+    wind.end();
+    wind.show();
+
+    let mut f = frame.clone();
+    frame.handle(Box::new(move |ev| match ev {
+        Event::Push => {
+            match event_clicks_num() {
+                0 => f.set_label("Single click"),
+                1 => f.set_label("Double click"),
+                _ => {
+                    f.set_label("Triple click");
+                    // Start counting again on the next click
+                    set_event_clicks(-1);
+                }
+            }
+            true
+        }
+        _ => false,
+    }));
+
+    app.run().unwrap();
+}
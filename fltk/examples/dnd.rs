@@ -0,0 +1,30 @@
+use fltk::{app::*, frame::*, window::*};
+
+fn main() {
+    let app = App::default();
+    let mut wind = Window::new(100, 100, 400, 300, "Drop text or files here");
+    let mut frame = Frame::new(0, 0, 400, 300, "Drop text or files here");
+    wind.end();
+    wind.show();
+
+    let mut f = frame.clone();
+    let mut dnd = false;
+    frame.handle(Box::new(move |ev| match ev {
+        // Accept the drop
+        Event::DndEnter | Event::DndDrag => true,
+        Event::DndRelease => {
+            dnd = true;
+            true
+        }
+        Event::Paste if dnd => {
+            dnd = false;
+            let text = event_dnd_text();
+            println!("Dropped: {}", text);
+            f.set_label(&text);
+            true
+        }
+        _ => false,
+    }));
+
+    app.run().unwrap();
+}
@@ -0,0 +1,16 @@
+use fltk::{app::*, menu::*, window::*};
+
+fn main() {
+    let app = App::default();
+    // Applies to the menus created from now on
+    set_menu_font(Font::Courier, 18);
+    let mut wind = Window::new(100, 100, 400, 300, "Menu font");
+    let mut bar = MenuBar::new(0, 0, 400, 30, "");
+    bar.add_choice("File|Edit|View|Help");
+    let mut chce = Choice::new(150, 120, 150, 30, "Choice");
+    chce.add_choice("First|Second|Third");
+    chce.set_value(0);
+    wind.end();
+    wind.show();
+    app.run().unwrap();
+}
@@ -0,0 +1,27 @@
+use fltk::{app::*, button::*, input::*, window::*};
+
+fn main() {
+    let app = App::default().with_scheme(Scheme::Gtk);
+    let mut wind = Window::new(100, 100, 400, 300, "Palette");
+    let inp = Input::new(100, 60, 200, 30, "Name:");
+    inp.set_value("Some text");
+    let mut light = Button::new(60, 160, 120, 40, "Light");
+    let mut dark = Button::new(220, 160, 120, 40, "Dark");
+    wind.end();
+    wind.show();
+
+    light.set_callback(Box::new(|| {
+        set_background(192, 192, 192);
+        set_background2(255, 255, 255);
+        set_foreground(0, 0, 0);
+        redraw();
+    }));
+    dark.set_callback(Box::new(|| {
+        set_background(40, 44, 52);
+        set_background2(30, 33, 39);
+        set_foreground(220, 223, 228);
+        redraw();
+    }));
+
+    app.run().unwrap();
+}
@@ -122,12 +122,22 @@ fn main() {
     wind.show();
 
     let mut term_c = term.clone();
+    let mut repeats = app::KeyRepeatFilter::new();
     term_c.handle(Box::new(move |ev| {
+        let repeat = repeats.is_repeat(ev);
         // println!("{:?}", app::event());
         // println!("{:?}", app::event_key());
         // println!("{:?}", app::event_text());
         match ev {
             Event::KeyDown => match app::event_key() {
+                // Holding Enter shouldn't run the command again
+                Key::Enter if repeat => true,
+                // Shift+Enter continues the command on the next line
+                Key::Enter if app::event_key_down(Key::ShiftL) || app::event_key_down(Key::ShiftR) => {
+                    term.append("\n");
+                    term.cmd.push(' ');
+                    true
+                }
                 Key::Enter => {
                     term.append("\n");
                     let out = term.run_command();
@@ -150,6 +160,8 @@ fn main() {
                         false
                     }
                 }
+                // Provisional input method text, only committed text is pushed
+                _ if app::is_composing() => true,
                 _ => {
                     let temp = app::event_text();
                     term.cmd.push_str(&temp);
@@ -8,7 +8,6 @@ struct Term {
     pub term: SimpleTerminal,
     current_dir: String,
     cmd: String,
-    sbuf: TextBuffer,
 }
 
 impl Term {
@@ -22,49 +21,47 @@ impl Term {
 
         let mut term = SimpleTerminal::new(5, 5, 630, 470, "");
 
-        let sbuf = TextBuffer::default();
-
         // Enable different colored text in TestDisplay
         let styles: Vec<StyleTableEntry> = vec![
             StyleTableEntry {
                 color: Color::Green,
                 font: Font::Courier,
                 size: 16,
+                ..Default::default()
             },
             StyleTableEntry {
                 color: Color::Red,
                 font: Font::Courier,
                 size: 16,
+                ..Default::default()
             },
             StyleTableEntry {
                 color: Color::from_u32(0x8000ff),
                 font: Font::Courier,
                 size: 16,
+                ..Default::default()
             },
         ];
 
-        term.set_highlight_data(sbuf.clone(), styles);
+        term.set_highlight_data(TextBuffer::default(), styles);
 
         Term {
             term,
             current_dir,
             cmd: String::from(""),
-            sbuf,
         }
     }
 
     fn append(&mut self, txt: &str) {
-        self.term.append(txt);
         if txt == self.current_dir.as_str() {
-            self.sbuf.append(&"C".repeat(txt.len()));
+            self.term.append_styled(txt, 'C');
         } else {
-            self.sbuf.append(&"A".repeat(txt.len()));
+            self.term.append_styled(txt, 'A');
         }
     }
 
     fn append_error(&mut self, txt: &str) {
-        self.term.append(txt);
-        self.sbuf.append(&"B".repeat(txt.len()));
+        self.term.append_styled(txt, 'B');
     }
 
     fn run_command(&mut self) -> String {
@@ -138,12 +135,16 @@ fn main() {
     wind.show();
 
     let mut term_c = term.clone();
+    let mut repeats = app::KeyRepeatFilter::new();
     term_c.handle(Box::new(move |ev| {
+        let repeat = repeats.is_repeat(ev);
         // println!("{:?}", app::event());
         // println!("{:?}", app::event_key());
         // println!("{:?}", app::event_text());
         match ev {
             Event::KeyDown => match app::event_key() {
+                // Holding Enter shouldn't run the command again
+                Key::Enter if repeat => true,
                 Key::Enter => {
                     term.append("\n");
                     let out = term.run_command();
@@ -161,13 +162,15 @@ fn main() {
                     if !term.cmd.is_empty() {
                         let text_len = term.text().len() as u32;
                         term.buffer().unwrap().remove(text_len - 1, text_len);
-                        term.sbuf.remove(text_len - 1, text_len);
+                        term.style_buffer().unwrap().remove(text_len - 1, text_len);
                         term.cmd.pop().unwrap();
                         true
                     } else {
                         false
                     }
                 }
+                // Provisional input method text, only committed text is pushed
+                _ if app::is_composing() => true,
                 _ => {
                     let temp = app::event_text();
                     term.cmd.push_str(&temp);
@@ -0,0 +1,20 @@
+use fltk::{app::*, button::*, window::*};
+
+fn main() {
+    let app = App::default();
+    set_tooltip_delay(0.2);
+    set_tooltip_hover_delay(0.05);
+    set_tooltip_wrap_width(200);
+
+    let mut wind = Window::new(100, 100, 400, 300, "Tooltips");
+    let mut but1 = Button::new(60, 130, 120, 40, "Short");
+    but1.set_tooltip("A short tooltip");
+    let mut but2 = Button::new(220, 130, 120, 40, "Long");
+    but2.set_tooltip(
+        "A much longer tooltip which is wrapped at 200 pixels instead of running across the whole screen",
+    );
+    wind.end();
+    wind.show();
+
+    app.run().unwrap();
+}
@@ -15,18 +15,81 @@ pub type WidgetPtr = *mut fltk_sys::widget::Fl_Widget;
 /// The fonts associated with the application
 pub(crate) static mut FONTS: Vec<String> = Vec::new();
 
-static mut LOADED_FONT: Option<&str> = None;
+/// The paths of the loaded fonts along with their font index
+static LOADED_FONTS: std::sync::Mutex<Vec<(String, usize)>> = std::sync::Mutex::new(Vec::new());
 
-/// Runs the event loop
+thread_local! {
+    static SCREEN_CONFIG_CB: std::cell::RefCell<Option<Box<dyn FnMut()>>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+thread_local! {
+    static QUIT_HOOKS: std::cell::RefCell<Vec<Box<dyn FnMut()>>> =
+        const { std::cell::RefCell::new(Vec::new()) };
+}
+
+static HANDLERS: std::sync::Mutex<Vec<fn(Event) -> bool>> = std::sync::Mutex::new(Vec::new());
+
+static CONFIRM_EXIT: std::sync::Mutex<Option<String>> = std::sync::Mutex::new(None);
+
+static EVENT_DISPATCH: std::sync::Mutex<Option<fn(Event, WidgetPtr) -> bool>> =
+    std::sync::Mutex::new(None);
+
+static BOX_DRAWS: std::sync::Mutex<[Option<fn(i32, i32, i32, i32, Color)>; 256]> =
+    std::sync::Mutex::new([None; 256]);
+
+static SCREEN_CONFIG: std::sync::Mutex<Vec<(i32, i32, i32, i32)>> =
+    std::sync::Mutex::new(Vec::new());
+
+static EXIT_CODE: std::sync::atomic::AtomicI32 = std::sync::atomic::AtomicI32::new(0);
+
+static DECIMAL_SEPARATOR: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new('.' as u32);
+
+static SCHEME_CYCLE_KEY: std::sync::atomic::AtomicI32 = std::sync::atomic::AtomicI32::new(0);
+
+/// Runs the event loop until all windows are closed.
+/// If a callback panics, the loop stops and the panic payload is returned as `FltkError::CallbackPanic`.
+/// This drives the loop with `Fl::wait` instead of calling `Fl::run`, so that panics are checked between iterations.
+/// Panics caught on any thread are reported, the first one winning
 pub fn run() -> Result<(), FltkError> {
     unsafe {
-        match Fl_run() {
-            0 => Ok(()),
-            _ => Err(FltkError::Internal(FltkErrorKind::FailedToRun)),
+        while !Fl_first_window().is_null() {
+            Fl_wait();
+            if let Some(payload) = take_callback_panic() {
+                return Err(FltkError::CallbackPanic(payload));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The first panic caught in a callback, process-wide since any thread waiting on events can run callbacks
+static CALLBACK_PANIC: std::sync::Mutex<Option<Box<dyn std::any::Any + Send>>> =
+    std::sync::Mutex::new(None);
+
+fn callback_panic_slot() -> std::sync::MutexGuard<'static, Option<Box<dyn std::any::Any + Send>>> {
+    CALLBACK_PANIC.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Runs a callback called from FLTK, catching a panic so that it doesn't unwind across the FFI boundary.
+/// The first caught panic is kept until `run` returns it
+pub(crate) fn catch_callback_panic<R>(f: impl FnOnce() -> R) -> Option<R> {
+    match std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)) {
+        Ok(ret) => Some(ret),
+        Err(payload) => {
+            let mut p = callback_panic_slot();
+            if p.is_none() {
+                *p = Some(payload);
+            }
+            None
         }
     }
 }
 
+fn take_callback_panic() -> Option<Box<dyn std::any::Any + Send>> {
+    callback_panic_slot().take()
+}
+
 /// Locks the main UI thread
 pub fn lock() -> Result<(), FltkError> {
     unsafe {
@@ -48,18 +111,38 @@ pub enum Scheme {
     Gtk,
     /// inspired by the Clearlooks Glossy scheme
     Gleam,
+    /// inspired by the Oxygen theme
+    Oxy,
 }
 
-/// sets the scheme of the application
+/// sets the scheme of the application.
+/// A scheme only changes how each frame type is drawn, e.g. the gradient of an `UpBox` under Plastic.
+/// It doesn't change the colors or frame types stored in widgets, so colors and frames set on individual widgets
+/// are kept, while widgets using the default frames take on the new scheme's look.
+/// Some schemes draw frames with gradients or images of their own, so a flat custom color may look lighter or darker
+/// than under the base scheme, in which case a `FlatBox`-style frame keeps it exact
 pub fn set_scheme(scheme: Scheme) {
     let name_str = match scheme {
         Scheme::Base => "base",
         Scheme::Gtk => "gtk+",
         Scheme::Gleam => "gleam",
+        Scheme::Oxy => "oxy",
         Scheme::Plastic => "plastic",
     };
-    let name_str = CString::safe_new(name_str).unwrap();
-    unsafe { Fl_set_scheme(name_str.as_ptr()) }
+    // An FLTK build lacking the scheme keeps the current one, like set_scheme_str
+    let _ = set_scheme_str(name_str);
+}
+
+/// Sets the scheme of the application by name, allowing schemes which have no `Scheme` variant yet.
+/// Returns an error if FLTK doesn't know the scheme, in which case the current scheme is kept
+pub fn set_scheme_str(name: &str) -> Result<(), FltkError> {
+    let name_str = CString::safe_new(name)?;
+    unsafe {
+        match Fl_set_scheme(name_str.as_ptr()) {
+            0 => Err(FltkError::Internal(FltkErrorKind::FailedToSetScheme)),
+            _ => Ok(()),
+        }
+    }
 }
 
 /// Gets the scheme of the application
@@ -71,11 +154,114 @@ pub fn scheme() -> Scheme {
             1 => Gtk,
             2 => Gleam,
             3 => Plastic,
+            4 => Oxy,
             _ => unreachable!(),
         }
     }
 }
 
+/// Returns the scheme following `current` in the cycle of `enable_scheme_cycle_key`
+fn next_scheme(current: Scheme) -> Scheme {
+    match current {
+        Scheme::Base => Scheme::Plastic,
+        Scheme::Plastic => Scheme::Gtk,
+        Scheme::Gtk => Scheme::Gleam,
+        Scheme::Gleam => Scheme::Oxy,
+        Scheme::Oxy => Scheme::Base,
+    }
+}
+
+/// Cycles through all schemes whenever the shortcut is pressed, redrawing the app each time.
+/// The cycle starts from the current scheme, so it follows schemes set with `set_scheme` in between.
+/// Meant as a development aid to check a UI under each scheme
+pub fn enable_scheme_cycle_key(shortcut: Shortcut) {
+    use std::sync::atomic::Ordering;
+    unsafe extern "C" fn shim(ev: raw::c_int) -> raw::c_int {
+        let key = SCHEME_CYCLE_KEY.load(Ordering::Relaxed);
+        if ev != Event::Shortcut as i32 || key == 0 || Fl_test_shortcut(key) == 0 {
+            return 0;
+        }
+        set_scheme(next_scheme(scheme()));
+        redraw();
+        1
+    }
+    static INSTALLED: std::sync::Once = std::sync::Once::new();
+    SCHEME_CYCLE_KEY.store(shortcut as i32, Ordering::Relaxed);
+    INSTALLED.call_once(|| unsafe { Fl_add_handler(Some(shim)) });
+}
+
+/// Disables the scheme cycling shortcut set by `enable_scheme_cycle_key`
+pub fn disable_scheme_cycle_key() {
+    SCHEME_CYCLE_KEY.store(0, std::sync::atomic::Ordering::Relaxed);
+}
+
+/// Applies a dark palette to the background, text background and foreground colors, or restores the default light one.
+/// The current scheme is reloaded so the change applies at runtime
+pub fn set_dark_mode(enabled: bool) {
+    unsafe {
+        if enabled {
+            Fl_background(50, 50, 50);
+            Fl_background2(30, 30, 30);
+            Fl_foreground(230, 230, 230);
+        } else {
+            Fl_background(192, 192, 192);
+            Fl_background2(255, 255, 255);
+            Fl_foreground(0, 0, 0);
+        }
+        Fl_reload_scheme();
+    }
+    redraw();
+}
+
+/// Sets the background color of the palette, used by widgets keeping the default `Color::BackGround`.
+/// Call `redraw` afterwards to repaint the shown windows
+pub fn set_background(r: u8, g: u8, b: u8) {
+    unsafe { Fl_background(r, g, b) }
+}
+
+/// Sets the text background color of the palette, used by input and browser widgets for instance.
+/// Call `redraw` afterwards to repaint the shown windows
+pub fn set_background2(r: u8, g: u8, b: u8) {
+    unsafe { Fl_background2(r, g, b) }
+}
+
+/// Sets the foreground color of the palette, used for labels and text keeping the default `Color::ForeGround`.
+/// Call `redraw` afterwards to repaint the shown windows
+pub fn set_foreground(r: u8, g: u8, b: u8) {
+    unsafe { Fl_foreground(r, g, b) }
+}
+
+/// Returns the red, green and blue components of `col` in the current palette,
+/// e.g. `Color::ForeGround` after a call to `set_foreground`
+pub fn get_color(col: Color) -> (u8, u8, u8) {
+    let (mut r, mut g, mut b) = (0, 0, 0);
+    unsafe { Fl_get_color_rgb(col as u32, &mut r, &mut g, &mut b) }
+    (r, g, b)
+}
+
+/// Replaces how boxes of type `old` are drawn, for every widget using it, with `new_draw`,
+/// which is called with the x, y, width and height of the box and the color to fill it with.
+/// The box keeps the frame thickness of `old`, and drawing is done with the `draw` module
+pub fn set_box_type(old: FrameType, new_draw: fn(i32, i32, i32, i32, Color)) {
+    unsafe extern "C" fn shim(
+        box_: raw::c_int,
+        x: raw::c_int,
+        y: raw::c_int,
+        w: raw::c_int,
+        h: raw::c_int,
+        c: raw::c_uint,
+    ) {
+        let draw = BOX_DRAWS.lock().unwrap_or_else(|e| e.into_inner())[(box_ & 255) as usize];
+        if let Some(f) = draw {
+            let c: Color = mem::transmute(c);
+            catch_callback_panic(|| f(x, y, w, h, c));
+        }
+    }
+    BOX_DRAWS.lock().unwrap_or_else(|e| e.into_inner())[(old as i32 & 255) as usize] =
+        Some(new_draw);
+    unsafe { Fl_set_box_type_cb(old as i32, Some(shim)) }
+}
+
 /// Alias Scheme to AppScheme
 pub type AppScheme = Scheme;
 
@@ -87,13 +273,14 @@ pub fn unlock() {
     }
 }
 
-/// Awakens the main UI thread with a callback
+/// Awakens the main UI thread with a callback.
+/// The callback is called once then freed
 pub fn awake(cb: Box<dyn FnMut()>) {
     unsafe {
         unsafe extern "C" fn shim(data: *mut raw::c_void) {
             let a: *mut Box<dyn FnMut()> = data as *mut Box<dyn FnMut()>;
-            let f: &mut (dyn FnMut()) = &mut **a;
-            let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f()));
+            let mut f: Box<Box<dyn FnMut()>> = Box::from_raw(a);
+            catch_callback_panic(|| f());
         }
         let a: *mut Box<dyn FnMut()> = Box::into_raw(Box::new(cb));
         let data: *mut raw::c_void = a as *mut raw::c_void;
@@ -102,13 +289,36 @@ pub fn awake(cb: Box<dyn FnMut()>) {
     }
 }
 
+/// Schedules a closure to run once on the main UI thread, can be called from any thread.
+/// The closure is freed after it runs
+pub fn run_on_main(cb: Box<dyn FnOnce() + Send>) {
+    unsafe {
+        unsafe extern "C" fn shim(data: *mut raw::c_void) {
+            let a: *mut Box<dyn FnOnce() + Send> = data as *mut Box<dyn FnOnce() + Send>;
+            let f: Box<dyn FnOnce() + Send> = *Box::from_raw(a);
+            catch_callback_panic(f);
+        }
+        let a: *mut Box<dyn FnOnce() + Send> = Box::into_raw(Box::new(cb));
+        let data: *mut raw::c_void = a as *mut raw::c_void;
+        let callback: Fl_Awake_Handler = Some(shim);
+        Fl_awake(callback, data);
+    }
+}
+
 /// Basic Application struct, used to instatiate, set the scheme and run the event loop
 #[derive(Debug, Copy, Clone)]
 pub struct App {}
 
 impl App {
-    /// Instantiates an App type
+    /// Instantiates an App type.
+    /// The display connection is opened lazily, when the first window is shown
     pub fn default() -> App {
+        App::default_with_options(false)
+    }
+
+    /// Instantiates an App type, opening the display connection right away if `open_display` is true.
+    /// Headless programs, or ones showing their GUI later, can pass false and call `app::open_display` when needed
+    pub fn default_with_options(open_display: bool) -> App {
         register_images();
         init_all();
         unsafe {
@@ -131,6 +341,9 @@ impl App {
                 "Zapfdingbats".to_owned(),
             ];
         }
+        if open_display {
+            self::open_display();
+        }
         App {}
     }
 
@@ -145,6 +358,23 @@ impl App {
         self
     }
 
+    /// Installs the platform's quit shortcut, Cmd+Q on macOS and Ctrl+Q elsewhere.
+    /// Pressing it calls `try_quit`, so the `set_confirm_exit` confirmation
+    /// and close callbacks which keep their window open are respected
+    pub fn with_quit_shortcut(self) -> App {
+        unsafe extern "C" fn shim(ev: raw::c_int) -> raw::c_int {
+            let shortcut = Shortcut::Command as i32 | 'q' as i32;
+            if ev != Event::Shortcut as i32 || Fl_test_shortcut(shortcut) == 0 {
+                return 0;
+            }
+            try_quit();
+            1
+        }
+        static INSTALLED: std::sync::Once = std::sync::Once::new();
+        INSTALLED.call_once(|| unsafe { Fl_add_handler(Some(shim)) });
+        self
+    }
+
     /// Gets the scheme of the application
     pub fn scheme(&self) -> Scheme {
         scheme()
@@ -156,6 +386,16 @@ impl App {
         run()
     }
 
+    /// Runs the event loop and returns the exit code set using app::set_exit_code, 0 by default.
+    /// Returns 1 if the event loop fails to run.
+    /// It doesn't call std::process::exit, the returned code can be passed to it
+    pub fn run_with_exit_code(&self) -> i32 {
+        match self.run() {
+            Ok(_) => exit_code(),
+            Err(_) => 1,
+        }
+    }
+
     /// Wait for incoming messages
     pub fn wait(&self) -> Result<bool, FltkError> {
         lock()?;
@@ -171,9 +411,9 @@ impl App {
     }
 
     /// Loads a font from a path.
-    /// On success, returns a String with the ttf Font Family name. The font's index is always 16.
-    /// As such only one font can be loaded at a time.
+    /// On success, returns a String with the ttf Font Family name.
     /// The font name can be used with Font::by_name, and index with Font::by_index.
+    /// To also get the assigned Font, use app::load_font
     /// # Examples
     /// ```
     /// use fltk::*;
@@ -183,15 +423,8 @@ impl App {
     /// frame.set_label_font(Font::by_name(&font));
     /// ```
     pub fn load_font(&self, path: &std::path::Path) -> Result<String, FltkError> {
-        if !path.exists() {
-            return Err::<String, FltkError>(FltkError::Internal(FltkErrorKind::ResourceNotFound));
-        }
-        if let Some(p) = path.to_str() {
-            let name = load_font(p)?;
-            Ok(name)
-        } else {
-            Err(FltkError::Internal(FltkErrorKind::ResourceNotFound))
-        }
+        let (_, name) = load_font(path)?;
+        Ok(name)
     }
 
     /// Set the visual of the application
@@ -199,19 +432,10 @@ impl App {
         set_visual(mode)
     }
 
-    /// Awakens the main UI thread with a callback
+    /// Awakens the main UI thread with a callback.
+    /// The callback is called once then freed
     pub fn awake(&self, cb: Box<dyn FnMut()>) {
-        unsafe {
-            unsafe extern "C" fn shim(data: *mut raw::c_void) {
-                let a: *mut Box<dyn FnMut()> = data as *mut Box<dyn FnMut()>;
-                let f: &mut (dyn FnMut()) = &mut **a;
-                let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f()));
-            }
-            let a: *mut Box<dyn FnMut()> = Box::into_raw(Box::new(cb));
-            let data: *mut raw::c_void = a as *mut raw::c_void;
-            let callback: Fl_Awake_Handler = Some(shim);
-            Fl_awake(callback, data);
-        }
+        awake(cb)
     }
 
     /// Returns the apps windows.
@@ -250,6 +474,12 @@ impl App {
     }
 }
 
+/// Returns whether `key` was held down when the latest event happened, not just the key which triggered it.
+/// For example Shift while Enter is pressed, modifiers can also be checked with `is_event_shift` and the like
+pub fn event_key_down(key: Key) -> bool {
+    unsafe { Fl_event_key_down(key as i32) != 0 }
+}
+
 /// Returns the latest captured event
 pub fn event() -> Event {
     unsafe {
@@ -259,7 +489,9 @@ pub fn event() -> Event {
     }
 }
 
-/// Returns the presed key
+/// Returns the presed key.
+/// Modifier keys are reported with their side, e.g. `Key::ShiftL` or `Key::ShiftR`,
+/// `Key::ControlL` or `Key::ControlR`, `Key::AltL` or `Key::AltR` and `Key::MetaL` or `Key::MetaR`
 pub fn event_key() -> Key {
     unsafe {
         let x = Fl_event_key();
@@ -267,6 +499,170 @@ pub fn event_key() -> Key {
     }
 }
 
+/// Tells auto-repeated `KeyDown` events from fresh key presses, which FLTK reports the same way.
+/// Feed it every event a widget's handler receives, for example to run a command once while Enter is held
+#[derive(Debug, Default, Clone)]
+pub struct KeyRepeatFilter {
+    held: Vec<i32>,
+}
+
+impl KeyRepeatFilter {
+    /// Creates a filter with no keys held
+    pub fn new() -> KeyRepeatFilter {
+        KeyRepeatFilter::default()
+    }
+
+    /// Records the current event and returns whether it's a `KeyDown` repeating a key which wasn't released.
+    /// Keys are considered released when the widget loses focus, since their `KeyUp` goes elsewhere
+    pub fn is_repeat(&mut self, ev: Event) -> bool {
+        let key = event_key() as i32;
+        match ev {
+            Event::KeyDown => {
+                if self.held.contains(&key) {
+                    true
+                } else {
+                    self.held.push(key);
+                    false
+                }
+            }
+            Event::KeyUp => {
+                self.held.retain(|k| *k != key);
+                false
+            }
+            Event::Unfocus | Event::Hide => {
+                self.held.clear();
+                false
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Builds a synthetic event and sends it to a widget's handler, mainly for testing UIs,
+/// e.g. clicking a button at (10, 10) then checking its label changed.
+/// Dispatching sets FLTK's event globals `Fl::e_number`, `Fl::e_x`, `Fl::e_y`, `Fl::e_x_root`, `Fl::e_y_root`,
+/// `Fl::e_keysym`, `Fl::e_state`, `Fl::e_clicks`, `Fl::e_is_click`, `Fl::e_text` and `Fl::e_length`,
+/// which stay set afterwards just like after a real event, so `event_x()`, `event_key()` and the others report them
+#[derive(Debug, Clone)]
+pub struct EventBuilder {
+    event: Event,
+    x: i32,
+    y: i32,
+    button: Option<MouseButton>,
+    key: Option<Key>,
+    modifiers: i32,
+    clicks: i32,
+    text: String,
+}
+
+impl EventBuilder {
+    /// Starts building an event of type `event`
+    pub fn new(event: Event) -> EventBuilder {
+        EventBuilder {
+            event,
+            x: 0,
+            y: 0,
+            button: None,
+            key: None,
+            modifiers: 0,
+            clicks: 0,
+            text: String::new(),
+        }
+    }
+
+    /// Sets the position of the event, relative to the window receiving it
+    pub fn with_pos(mut self, x: i32, y: i32) -> EventBuilder {
+        self.x = x;
+        self.y = y;
+        self
+    }
+
+    /// Sets the mouse button of the event, which is also reported as held
+    pub fn with_button(mut self, button: MouseButton) -> EventBuilder {
+        self.button = Some(button);
+        self
+    }
+
+    /// Sets the key of the event
+    pub fn with_key(mut self, key: Key) -> EventBuilder {
+        self.key = Some(key);
+        self
+    }
+
+    /// Sets the modifiers held during the event
+    pub fn with_modifiers(mut self, modifiers: Shortcut) -> EventBuilder {
+        self.modifiers = modifiers as i32;
+        self
+    }
+
+    /// Sets the number of extra clicks, 1 for a double click
+    pub fn with_clicks(mut self, clicks: i32) -> EventBuilder {
+        self.clicks = clicks;
+        self
+    }
+
+    /// Sets the text of the event, as typed by a key press or pasted
+    pub fn with_text(mut self, text: &str) -> EventBuilder {
+        self.text = text.to_string();
+        self
+    }
+
+    fn set_globals(&self, root_x: i32, root_y: i32) {
+        // FLTK reports mouse buttons as keys following FL_Button, and held buttons in the state
+        let (keysym, state) = match self.button {
+            Some(b) => (0xfee8 + b as i32, self.modifiers | (0x0080_0000 << b as i32)),
+            None => (self.key.map(|k| k as i32).unwrap_or(0), self.modifiers),
+        };
+        let text = CString::safe_new(&self.text).unwrap();
+        unsafe {
+            Fl_set_event(
+                self.event as i32,
+                self.x,
+                self.y,
+                root_x + self.x,
+                root_y + self.y,
+                keysym,
+                state,
+                self.clicks,
+                text.as_ptr(),
+            )
+        }
+    }
+
+    /// Sends the event straight to the handler of `widget`, returning whether it was handled
+    pub fn dispatch_to<W: WidgetExt>(&self, widget: &mut W) -> bool {
+        assert!(!widget.was_deleted());
+        let (root_x, root_y) = match widget.window() {
+            Some(win) => {
+                let (x, y, _, _) = win.screen_xywh();
+                (x, y)
+            }
+            None => (0, 0),
+        };
+        self.set_globals(root_x, root_y);
+        unsafe {
+            Fl_handle_widget(
+                widget.as_widget_ptr() as *mut fltk_sys::fl::Fl_Widget,
+                self.event as i32,
+            ) != 0
+        }
+    }
+
+    /// Sends the event through FLTK's normal dispatching to the first window, returning whether it was handled.
+    /// Like a real event, it reaches the widget under the position, the focused widget or the global handlers
+    pub fn dispatch(&self) -> bool {
+        let (root_x, root_y) = match first_window() {
+            Some(win) => {
+                let (x, y, _, _) = win.screen_xywh();
+                (x, y)
+            }
+            None => (0, 0),
+        };
+        self.set_globals(root_x, root_y);
+        unsafe { Fl_handle_event(self.event as i32) != 0 }
+    }
+}
+
 /// Returns a textual representation of the latest event
 pub fn event_text() -> String {
     unsafe {
@@ -281,12 +677,134 @@ pub fn event_text() -> String {
     }
 }
 
+/// Returns the content dropped on a widget, read from the text of the `Paste` event following a `DndRelease`.
+/// Invalid UTF-8 is replaced, and an empty string is returned outside of a `Paste` event.
+/// To get dropped files as paths, use `event_dropped_files`
+pub fn event_dnd_text() -> String {
+    if event() != Event::Paste {
+        return String::new();
+    }
+    unsafe {
+        let text = Fl_event_text();
+        let len = Fl_event_length();
+        if text.is_null() || len <= 0 {
+            String::new()
+        } else {
+            let bytes = std::slice::from_raw_parts(text as *const u8, len as usize);
+            String::from_utf8_lossy(bytes).to_string()
+        }
+    }
+}
+
+/// Returns the paths of the files dropped on a widget, decoded from the text of the `Paste` event following a `DndRelease`.
+/// X11 file managers send `file://` URIs with percent-encoded characters, while Windows and macOS send plain paths,
+/// both are converted to plain paths here. Entries which aren't local files are skipped
+pub fn event_dropped_files() -> Vec<std::path::PathBuf> {
+    parse_dropped_files(&event_text())
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+            if let Some(b) = hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                out.push(b);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
+
+fn parse_dropped_files(text: &str) -> Vec<std::path::PathBuf> {
+    text.lines()
+        .map(|l| l.trim_end_matches('\r'))
+        .filter(|l| !l.is_empty())
+        .filter_map(|l| {
+            if let Some(uri) = l.strip_prefix("file://") {
+                // Skip the host part, usually empty or "localhost"
+                let path = percent_decode(&uri[uri.find('/')?..]);
+                // Windows URIs look like file:///C:/dir
+                if cfg!(target_os = "windows") && path.get(2..3) == Some(":") {
+                    return Some(std::path::PathBuf::from(&path[1..]));
+                }
+                Some(std::path::PathBuf::from(path))
+            } else if l.contains("://") {
+                None
+            } else {
+                Some(std::path::PathBuf::from(l))
+            }
+        })
+        .collect()
+}
+
+/// Returns whether a text composition, e.g. by an input method, is in progress.
+/// While composing, the event text is provisional and shouldn't be treated as committed input
+pub fn is_composing() -> bool {
+    unsafe { Fl_compose_state() != 0 }
+}
+
+/// Returns why the currently running callback was called, for example to tell an input's Enter key from losing focus.
+/// Only meaningful inside a callback
+pub fn callback_reason() -> CallbackReason {
+    CallbackReason::from_i32(unsafe { Fl_callback_reason() })
+}
+
+/// Sends `event` to the handler of `wid`, returning whether it was handled.
+/// The event reuses the current event globals, such as the key and position, use `EventBuilder` to set them too
+pub fn handle<W: WidgetExt>(event: Event, wid: &W) -> bool {
+    assert!(!wid.was_deleted());
+    unsafe { Fl_handle_widget(wid.as_widget_ptr() as *mut fltk_sys::fl::Fl_Widget, event as i32) != 0 }
+}
+
 /// Returns the captured button event
 pub fn event_button() -> i32 {
     unsafe { Fl_event_button() }
 }
 
-/// Returns the number of clicks
+/// Returns the mouse button of the captured button event, including the back and forward buttons
+pub fn event_mouse_button() -> MouseButton {
+    MouseButton::from_i32(event_button())
+}
+
+/// Returns whether `b` is the mouse button of the captured button event
+pub fn event_button_is(b: MouseButton) -> bool {
+    event_button() == b as i32
+}
+
+/// Returns whether the left mouse button was held down during the captured event
+pub fn event_button1() -> bool {
+    unsafe { Fl_event_button1() != 0 }
+}
+
+/// Returns whether the middle mouse button was held down during the captured event
+pub fn event_button2() -> bool {
+    unsafe { Fl_event_button2() != 0 }
+}
+
+/// Returns whether the right mouse button was held down during the captured event
+pub fn event_button3() -> bool {
+    unsafe { Fl_event_button3() != 0 }
+}
+
+/// Returns the number of clicks after the first one, i.e. 0 for a single click, 1 for a double click,
+/// 2 for a triple click, and so on
+pub fn event_clicks_num() -> i32 {
+    unsafe { Fl_event_clicks() }
+}
+
+/// Sets the click counter returned by `event_clicks_num`, e.g. to 0 so the next click is seen as a single one
+pub fn set_event_clicks(n: i32) {
+    unsafe { Fl_set_event_clicks(n) }
+}
+
+/// Returns whether the current click is part of a multiple click, see `event_clicks_num` for the count
 pub fn event_clicks() -> bool {
     unsafe {
         match Fl_event_clicks() {
@@ -296,7 +814,8 @@ pub fn event_clicks() -> bool {
     }
 }
 
-/// Gets the x coordinate of the mouse in the window
+/// Gets the x coordinate of the mouse in the window.
+/// Event coordinates are in FLTK units, already divided by the screen's scale factor
 pub fn event_x() -> i32 {
     unsafe {
         Fl_event_x()
@@ -310,6 +829,19 @@ pub fn event_y() -> i32 {
     }
 }
 
+/// Gets the x coordinate of the mouse in the window as a floating point value.
+/// FLTK currently only reports whole coordinates, so this is `event_x` as f64,
+/// but it lets drawing code be written for sub-pixel input once it is available
+pub fn event_x_f() -> f64 {
+    event_x() as f64
+}
+
+/// Gets the y coordinate of the mouse in the window as a floating point value.
+/// FLTK currently only reports whole coordinates, so this is `event_y` as f64
+pub fn event_y_f() -> f64 {
+    event_y() as f64
+}
+
 /// Gets the x coordinate of the mouse in the screen
 pub fn event_x_root() -> i32 {
     unsafe {
@@ -324,6 +856,22 @@ pub fn event_y_root() -> i32 {
     }
 }
 
+/// Gets the x coordinate of the mouse in the window in physical pixels,
+/// i.e. scaled by the scale factor of the screen the event occured in.
+/// Use it to correlate events with pixel data like images, otherwise event_x should be used
+pub fn event_x_pixels() -> i32 {
+    let scale = screen_scale(screen_num(event_x_root(), event_y_root()));
+    (event_x() as f32 * scale).round() as i32
+}
+
+/// Gets the y coordinate of the mouse in the window in physical pixels,
+/// i.e. scaled by the scale factor of the screen the event occured in.
+/// Use it to correlate events with pixel data like images, otherwise event_y should be used
+pub fn event_y_pixels() -> i32 {
+    let scale = screen_scale(screen_num(event_x_root(), event_y_root()));
+    (event_y() as f32 * scale).round() as i32
+}
+
 /// Gets the difference in x axis of the mouse coordinates from the screen to the window
 pub fn event_dx() -> i32 {
     unsafe {
@@ -338,7 +886,7 @@ pub fn event_dy() -> i32 {
     }
 }
 
-/// Gets the mouse coordinates relative to the screen
+/// Gets the mouse coordinates relative to the screen, in FLTK units
 pub fn get_mouse() -> (i32, i32) {
     unsafe {
         let mut x: i32 = 0;
@@ -373,9 +921,192 @@ pub fn event_state() -> Shortcut {
     unsafe { mem::transmute(Fl_event_state()) }
 }
 
-/// Returns a pair of the width and height of the screen
+/// Returns a pair of the width and height of the main screen, in FLTK units like every other coordinate of this module.
+/// Multiply by `screen_scale(0)` to get physical pixels, and use `screen_xywh` for the bounds of other screens
 pub fn screen_size() -> (f64, f64) {
-    unsafe { ((Fl_screen_w() as f64 / 0.96), (Fl_screen_h() as f64 / 0.96)) }
+    unsafe { (Fl_screen_w() as f64, Fl_screen_h() as f64) }
+}
+
+/// Returns the index of the screen containing the point x, y
+pub fn screen_num(x: i32, y: i32) -> i32 {
+    unsafe { Fl_screen_num(x, y) }
+}
+
+/// Returns whether an X11 `DISPLAY` value points to another machine.
+/// SSH X forwarding uses a `localhost` display numbered from 10, so `ssh` marks those as remote too
+fn display_is_remote(display: &str, ssh: bool) -> bool {
+    let (host, number) = match display.rfind(':') {
+        Some(pos) => (&display[..pos], &display[pos + 1..]),
+        None => return false,
+    };
+    let number: i32 = number.split('.').next().unwrap_or("").parse().unwrap_or(0);
+    match host {
+        "" | "unix" => false,
+        "localhost" | "127.0.0.1" | "::1" => ssh && number >= 10,
+        // XQuartz and similar launchd sockets
+        h if h.starts_with('/') => false,
+        _ => true,
+    }
+}
+
+/// Heuristically detects whether the app is displayed on another machine,
+/// such as an SSH-forwarded or remote X11 display, or a Windows Remote Desktop session.
+/// Useful for disabling animations and other bandwidth-heavy drawing.
+/// Returns false when it can't tell
+pub fn is_remote_display() -> bool {
+    if cfg!(target_os = "windows") {
+        std::env::var("SESSIONNAME")
+            .map(|s| s.to_ascii_uppercase().starts_with("RDP-"))
+            .unwrap_or(false)
+    } else if cfg!(target_os = "macos") {
+        false
+    } else {
+        let ssh = std::env::var_os("SSH_CONNECTION").is_some()
+            || std::env::var_os("SSH_CLIENT").is_some();
+        std::env::var("DISPLAY")
+            .map(|d| display_is_remote(&d, ssh))
+            .unwrap_or(false)
+    }
+}
+
+/// Returns the number of screens
+pub fn screen_count() -> i32 {
+    unsafe { Fl_screen_count() }
+}
+
+/// Returns the x, y, width and height of screen `n`, in the coordinates windows are placed in
+pub fn screen_xywh(n: i32) -> (i32, i32, i32, i32) {
+    let (mut x, mut y, mut w, mut h) = (0, 0, 0, 0);
+    unsafe { Fl_screen_xywh(&mut x, &mut y, &mut w, &mut h, n) }
+    (x, y, w, h)
+}
+
+/// Returns the index of the screen under the mouse cursor, falling back to the main screen (0)
+pub fn mouse_screen() -> i32 {
+    let (x, y) = get_mouse();
+    let n = screen_num(x, y);
+    if n >= 0 && n < screen_count() {
+        n
+    } else {
+        0
+    }
+}
+
+/// Returns the x, y, width and height of the work area of screen `n`, excluding taskbars and docks
+pub fn screen_work_area(n: i32) -> (i32, i32, i32, i32) {
+    let (mut x, mut y, mut w, mut h) = (0, 0, 0, 0);
+    unsafe { Fl_screen_work_area(&mut x, &mut y, &mut w, &mut h, n) }
+    (x, y, w, h)
+}
+
+/// Snapshot of every screen's work area, compared to detect configuration changes
+fn screen_config() -> Vec<(i32, i32, i32, i32)> {
+    (0..screen_count()).map(screen_work_area).collect()
+}
+
+fn screen_config_changed() {
+    *SCREEN_CONFIG.lock().unwrap_or_else(|e| e.into_inner()) = screen_config();
+    // The callback is taken out while it runs, so it may replace itself
+    if let Some(mut cb) = SCREEN_CONFIG_CB.with(|c| c.borrow_mut().take()) {
+        catch_callback_panic(|| cb());
+        SCREEN_CONFIG_CB.with(|c| {
+            let mut c = c.borrow_mut();
+            if c.is_none() {
+                *c = Some(cb);
+            }
+        });
+    }
+}
+
+/// Sets a callback invoked when the screen configuration changes,
+/// for example when a monitor is plugged in or out or a screen's resolution changes.
+/// On Windows, macOS and X11 with Xrandr this follows the `Event::ScreenConfigChanged` event FLTK receives from the OS.
+/// Since X11 servers without Xrandr don't report such changes, on X11 the screens are also polled every second
+pub fn set_screen_config_callback(cb: Box<dyn FnMut()>) {
+    unsafe extern "C" fn handler(ev: raw::c_int) -> raw::c_int {
+        if ev == Event::ScreenConfigChanged as i32 {
+            screen_config_changed();
+        }
+        0
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    unsafe extern "C" fn poll(_data: *mut raw::c_void) {
+        let changed = screen_config() != *SCREEN_CONFIG.lock().unwrap_or_else(|e| e.into_inner());
+        if changed {
+            screen_config_changed();
+        }
+        Fl_repeat_timeout(1.0, Some(poll), std::ptr::null_mut());
+    }
+    static INSTALLED: std::sync::Once = std::sync::Once::new();
+    SCREEN_CONFIG_CB.with(|c| *c.borrow_mut() = Some(cb));
+    INSTALLED.call_once(|| unsafe {
+        *SCREEN_CONFIG.lock().unwrap_or_else(|e| e.into_inner()) = screen_config();
+        Fl_add_handler(Some(handler));
+        #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+        Fl_add_timeout(1.0, Some(poll), std::ptr::null_mut());
+    });
+}
+
+/// Captures the screen region at `x`, `y` of size `w` by `h` into an image, including other applications' windows.
+/// To capture the app's own drawing, use `draw::capture_window` instead.
+/// On macOS 10.15 and later this needs the Screen Recording permission,
+/// without it only the desktop and the app's own windows are captured.
+/// Returns None if the capture fails
+pub fn screenshot(x: i32, y: i32, w: i32, h: i32) -> Option<crate::image::RgbImage> {
+    if w <= 0 || h <= 0 {
+        return None;
+    }
+    let mut buf = vec![0u8; w as usize * h as usize * 3];
+    unsafe {
+        if Fl_capture_screen(buf.as_mut_ptr(), x, y, w, h) == 0 {
+            return None;
+        }
+    }
+    crate::image::RgbImage::new(&buf, w as u32, h as u32, 3).ok()
+}
+
+/// Returns the scale factor of the screen `n`, the number of physical pixels per FLTK unit
+pub fn screen_scale(n: i32) -> f32 {
+    unsafe { Fl_screen_scale(n) }
+}
+
+/// Sets the scale factor of the screen `n`, rescaling the windows shown on it.
+/// Coordinates and sizes reported by the app module stay in FLTK units, so they shrink as the factor grows
+pub fn set_screen_scale(n: i32, factor: f32) {
+    unsafe { Fl_set_screen_scale(n, factor) }
+}
+
+/// Sets the decimal separator used when numbers are parsed from or formatted for input widgets, e.g. ',' for many European locales.
+/// FLTK itself always uses '.', this only affects `parse_decimal`, `format_decimal` and the `FloatInput` value helpers
+pub fn set_decimal_separator(c: char) {
+    DECIMAL_SEPARATOR.store(c as u32, std::sync::atomic::Ordering::Relaxed);
+}
+
+/// Returns the decimal separator set by `set_decimal_separator`, '.' by default
+pub fn decimal_separator() -> char {
+    std::char::from_u32(DECIMAL_SEPARATOR.load(std::sync::atomic::Ordering::Relaxed)).unwrap_or('.')
+}
+
+/// Parses a number entered by the user, accepting the decimal separator as well as '.'
+pub fn parse_decimal(s: &str) -> Option<f64> {
+    let sep = decimal_separator();
+    let s = s.trim();
+    if sep == '.' {
+        s.parse().ok()
+    } else {
+        s.replace(sep, ".").parse().ok()
+    }
+}
+
+/// Formats a number for display using the decimal separator
+pub fn format_decimal(v: f64) -> String {
+    let sep = decimal_separator();
+    let s = v.to_string();
+    if sep == '.' {
+        s
+    } else {
+        s.replace('.', &sep.to_string())
+    }
 }
 
 /// Used for widgets implementing the InputExt, pastes content from the clipboard
@@ -389,6 +1120,125 @@ where
     }
 }
 
+/// Pastes content from `source` into `widget`, which receives it as an `Event::Paste` with the text in `event_text`.
+/// Custom widgets can use it with `ClipboardSource::Selection` to paste the primary selection on middle-click
+pub fn paste_from<T: WidgetExt>(widget: &T, source: ClipboardSource) {
+    assert!(!widget.was_deleted());
+    unsafe {
+        Fl_paste(
+            widget.as_widget_ptr() as *mut fltk_sys::fl::Fl_Widget,
+            source as i32,
+        );
+    }
+}
+
+/// Used for widgets implementing the InputExt, pastes content from the primary selection.
+/// On Windows and macOS there's no primary selection, so this pastes from the clipboard
+pub fn paste_primary<T>(widget: &T)
+where
+    T: WidgetExt + InputExt,
+{
+    paste_from(widget, ClipboardSource::Selection)
+}
+
+/// Returns whether the current event is a middle-click which should paste the primary selection.
+/// This is the X11 convention, so it's always false on Windows and macOS
+pub fn is_middle_click_paste() -> bool {
+    cfg!(not(any(target_os = "windows", target_os = "macos")))
+        && event() == Event::Push
+        && event_button_is(MouseButton::Middle)
+}
+
+/// Puts `text` on the clipboard
+pub fn copy(text: &str) {
+    unsafe { Fl_copy(text.as_ptr() as *const raw::c_char, text.len() as i32, 1) }
+}
+
+/// Puts `text` in the primary selection, which is pasted on middle-click on X11.
+/// On Windows and macOS there's no primary selection, so this puts `text` on the clipboard
+pub fn set_selection(text: &str) {
+    unsafe {
+        Fl_copy(
+            text.as_ptr() as *const raw::c_char,
+            text.len() as i32,
+            ClipboardSource::Selection as i32,
+        )
+    }
+}
+
+/// Puts several representations of the same content on the clipboard, each given as a MIME type and its bytes.
+/// Only plain text is supported, since FLTK's clipboard holds a single text buffer:
+/// the first `text/plain` representation (with or without a charset) is copied and the other formats are ignored,
+/// so pasting applications receive the plain text version.
+/// Returns false without touching the clipboard if there's no plain text representation
+pub fn copy_multi(representations: &[(&str, &[u8])]) -> bool {
+    let text = representations.iter().find(|(mime, _)| {
+        let mime = mime.split(';').next().unwrap_or("").trim();
+        mime.eq_ignore_ascii_case("text/plain")
+    });
+    match text {
+        Some((_, bytes)) => {
+            unsafe { Fl_copy(bytes.as_ptr() as *const raw::c_char, bytes.len() as i32, 1) }
+            true
+        }
+        None => false,
+    }
+}
+
+/// Passes the text content of the clipboard to `f`, returns None if the clipboard has no text
+fn with_clipboard<R, F: FnOnce(&[u8]) -> R>(f: F) -> Option<R> {
+    unsafe {
+        let mut len: raw::c_int = 0;
+        let ptr = Fl_clipboard_text(&mut len);
+        if ptr.is_null() {
+            None
+        } else {
+            Some(f(std::slice::from_raw_parts(ptr as *const u8, len as usize)))
+        }
+    }
+}
+
+/// Returns the text content of the clipboard, or an empty string if the clipboard is empty or unavailable.
+/// The whole content is copied, which can be large if another application placed a lot of text on the clipboard,
+/// use clipboard_text_limited to cap it.
+/// # Reentrancy
+/// On X11 the content is delivered asynchronously, so this runs the event loop, waiting 10ms at a time,
+/// until it arrives or for up to a second, and an empty string is returned on timeout.
+/// Timeouts, idle callbacks, channel messages and events for other widgets can be handled during the call,
+/// so callers must not hold borrows (e.g. a `RefCell`) which those callbacks might also take.
+/// Use `paste_from` to receive the content asynchronously as an `Event::Paste` instead
+pub fn clipboard_text() -> String {
+    with_clipboard(|bytes| String::from_utf8_lossy(bytes).to_string()).unwrap_or_default()
+}
+
+/// Returns at most `max_bytes` of the clipboard's text content, truncated at a UTF-8 character boundary.
+/// Returns None if the clipboard is empty or unavailable.
+/// Like `clipboard_text`, this can run the event loop while waiting for the content
+pub fn clipboard_text_limited(max_bytes: usize) -> Option<String> {
+    with_clipboard(|bytes| {
+        let mut end = std::cmp::min(bytes.len(), max_bytes);
+        while end > 0 && end < bytes.len() && (bytes[end] & 0xC0) == 0x80 {
+            end -= 1;
+        }
+        String::from_utf8_lossy(&bytes[..end]).to_string()
+    })
+}
+
+/// Returns the raw bytes of the clipboard's text content without any UTF-8 conversion.
+/// Returns None if the clipboard is empty or unavailable.
+/// Like `clipboard_text`, this can run the event loop while waiting for the content
+pub fn clipboard_bytes() -> Option<Vec<u8>> {
+    with_clipboard(|bytes| bytes.to_vec())
+}
+
+/// Returns the text content of the clipboard, failing instead of replacing invalid UTF-8 sequences.
+/// The raw bytes can be recovered from the error using `FromUtf8Error::into_bytes`.
+/// Returns Ok(None) if the clipboard is empty or unavailable.
+/// Like `clipboard_text`, this can run the event loop while waiting for the content
+pub fn clipboard_text_checked() -> Result<Option<String>, std::string::FromUtf8Error> {
+    clipboard_bytes().map(String::from_utf8).transpose()
+}
+
 /// Sets the callback of a widget
 pub fn set_callback<W>(widget: &mut W, cb: Box<dyn FnMut()>)
 where
@@ -399,7 +1249,7 @@ where
         unsafe extern "C" fn shim(_wid: *mut fltk_sys::widget::Fl_Widget, data: *mut raw::c_void) {
             let a: *mut Box<dyn FnMut()> = data as *mut Box<dyn FnMut()>;
             let f: &mut (dyn FnMut()) = &mut **a;
-            let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f()));
+            catch_callback_panic(|| f());
         }
         widget.unset_callback();
         let a: *mut Box<dyn FnMut()> = Box::into_raw(Box::new(cb));
@@ -421,12 +1271,37 @@ where
     fltk_sys::widget::Fl_Widget_callback_with_captures(widget.as_widget_ptr(), cb, data);
 }
 
+/// Sets the text font and size of menu, choice and menu bar widgets created afterwards
+pub fn set_menu_font(font: Font, size: i32) {
+    unsafe { fltk_sys::menu::Fl_set_menu_font(font.bits(), size) }
+}
+
 /// Initializes loaded fonts of a certain pattern ```name```
 pub fn set_fonts(name: &str) -> u8 {
     let name = CString::safe_new(name).unwrap();
     unsafe { Fl_set_fonts(name.as_ptr() as *mut raw::c_char) as u8 }
 }
 
+/// Sets the default font size of widgets, which applies to the label and text of widgets created afterwards
+pub fn set_font_size(sz: i32) {
+    unsafe { Fl_set_font_size(sz) }
+}
+
+/// Gets the default font size of widgets
+pub fn font_size() -> i32 {
+    unsafe { Fl_font_size() }
+}
+
+/// Sets the extra vertical space between menu items, in pixels
+pub fn set_menu_linespacing(h: i32) {
+    unsafe { Fl_set_menu_linespacing(h) }
+}
+
+/// Gets the extra vertical space between menu items, in pixels
+pub fn menu_linespacing() -> i32 {
+    unsafe { Fl_menu_linespacing() }
+}
+
 /// Gets the name of a font through its index
 pub fn font_name(idx: usize) -> Option<String> {
     unsafe {
@@ -448,15 +1323,55 @@ pub fn get_font_names() -> Vec<String> {
     vec
 }
 
-/// Finds the index of a font through its name
+/// Finds the index of a font through its name, ignoring case
 pub fn font_index(name: &str) -> Option<usize> {
     unsafe {
-        FONTS.iter().position(|i| i == name)
+        FONTS.iter().position(|i| i.eq_ignore_ascii_case(name))
     }
 }
 
-/// Gets the number of loaded fonts
-pub fn font_count() -> usize {
+/// Finds the index of the font closest to `name`, along with whether the match is exact (ignoring case).
+/// Inexact matches ignore spacing and punctuation, then style suffixes such as "Bold" or "Italic"
+pub fn closest_font_index(name: &str) -> Option<(usize, bool)> {
+    unsafe { closest_font(&FONTS, name) }
+}
+
+fn closest_font(fonts: &[String], name: &str) -> Option<(usize, bool)> {
+    const STYLES: [&str; 8] = [
+        "regular", "normal", "medium", "bold", "italic", "oblique", "light", "book",
+    ];
+    fn normalize(name: &str) -> String {
+        name.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(|c| c.to_lowercase())
+            .collect()
+    }
+    fn strip_styles(name: &str) -> String {
+        let mut name = normalize(name);
+        while let Some(style) = STYLES
+            .iter()
+            .find(|s| name.len() > s.len() && name.ends_with(*s))
+        {
+            name.truncate(name.len() - style.len());
+        }
+        name
+    }
+    if let Some(idx) = fonts.iter().position(|f| f.eq_ignore_ascii_case(name)) {
+        return Some((idx, true));
+    }
+    let normalized = normalize(name);
+    if let Some(idx) = fonts.iter().position(|f| normalize(f) == normalized) {
+        return Some((idx, false));
+    }
+    let stripped = strip_styles(name);
+    fonts
+        .iter()
+        .position(|f| strip_styles(f) == stripped)
+        .map(|idx| (idx, false))
+}
+
+/// Gets the number of loaded fonts
+pub fn font_count() -> usize {
     unsafe {
         FONTS.len()
     }
@@ -467,14 +1382,113 @@ pub fn fonts() -> Vec<String> {
     unsafe { FONTS.clone() }
 }
 
-/// Adds a custom handler for unhandled events
+// FLTK only accepts bare function pointers, so the registered `fn`s live in `HANDLERS`
+unsafe extern "C" fn handlers_shim(ev: raw::c_int) -> raw::c_int {
+    let ev: Event = mem::transmute(ev);
+    // Copied out so that handlers can add or remove handlers
+    let handlers = HANDLERS.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    for cb in handlers {
+        if catch_callback_panic(|| cb(ev)).unwrap_or(false) {
+            return 1;
+        }
+    }
+    0
+}
+
+/// Adds a custom handler for unhandled events, handlers are tried in the order they were added
 pub fn add_handler(cb: fn(Event) -> bool) {
+    let mut handlers = HANDLERS.lock().unwrap_or_else(|e| e.into_inner());
+    if handlers.is_empty() {
+        unsafe { Fl_add_handler(Some(handlers_shim)) }
+    }
+    handlers.push(cb);
+}
+
+/// Sets a global event interceptor which sees every event before any widget, along with the window it's sent to,
+/// which is null for events not targeting a window.
+/// Returning false swallows the event, while returning true passes it on to normal handling.
+/// Unlike `add_handler`, which only sees unhandled events, this is called for all of them, so it should return quickly.
+/// Only one interceptor can be set, use `reset_event_dispatch` to remove it.
+/// Setting or removing it leaves `set_focus_follows_mouse` in effect
+pub fn set_event_dispatch(cb: fn(Event, WidgetPtr) -> bool) {
+    unsafe extern "C" fn shim(ev: raw::c_int, win: *mut raw::c_void) -> raw::c_int {
+        let ev: Event = mem::transmute(ev);
+        let dispatch = *EVENT_DISPATCH.lock().unwrap_or_else(|e| e.into_inner());
+        match dispatch {
+            Some(cb) => catch_callback_panic(|| cb(ev, win as WidgetPtr)).unwrap_or(true) as i32,
+            None => 1,
+        }
+    }
+    *EVENT_DISPATCH.lock().unwrap_or_else(|e| e.into_inner()) = Some(cb);
+    unsafe { Fl_event_dispatch(Some(shim)) }
+}
+
+/// Removes the event interceptor set with `set_event_dispatch`
+pub fn reset_event_dispatch() {
+    unsafe { Fl_event_dispatch(None) }
+    *EVENT_DISPATCH.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Removes a handler added with `add_handler`, so it no longer sees unhandled events
+pub fn remove_handler(cb: fn(Event) -> bool) {
+    let mut handlers = HANDLERS.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(pos) = handlers.iter().position(|h| *h as usize == cb as usize) {
+        handlers.remove(pos);
+        if handlers.is_empty() {
+            unsafe { Fl_remove_handler(Some(handlers_shim)) }
+        }
+    }
+}
+
+/// A system-wide hotkey registered with `register_global_hotkey`
+#[derive(Debug)]
+pub struct HotkeyHandle {
+    id: i32,
+}
+
+impl HotkeyHandle {
+    /// Unregisters the hotkey, releasing the key combination for other applications
+    pub fn unregister(self) {
+        unsafe {
+            let data = Fl_unregister_global_hotkey(self.id);
+            if !data.is_null() {
+                let _ = Box::from_raw(data as *mut Box<dyn FnMut()>);
+            }
+        }
+    }
+}
+
+/// Registers a system-wide hotkey which calls `cb` even while the app isn't focused.
+/// Uses RegisterHotKey on Windows, Carbon hot keys on macOS and a key grab on the X11 root window.
+/// The hotkey stays active until `HotkeyHandle::unregister` is called.
+/// Returns an error if the key can't be mapped, is already taken by another application,
+/// or if the platform doesn't support global hotkeys
+pub fn register_global_hotkey(
+    shortcut: Shortcut,
+    cb: Box<dyn FnMut()>,
+) -> Result<HotkeyHandle, FltkError> {
     unsafe {
-        let callback: Option<unsafe extern "C" fn(ev: raw::c_int) -> raw::c_int> =
-            Some(mem::transmute(move |ev| {
-                let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| cb(ev) as i32));
-            }));
-        Fl_add_handler(callback);
+        unsafe extern "C" fn shim(data: *mut raw::c_void) {
+            let a: *mut Box<dyn FnMut()> = data as *mut Box<dyn FnMut()>;
+            let f: &mut (dyn FnMut()) = &mut **a;
+            catch_callback_panic(|| f());
+        }
+        let a: *mut Box<dyn FnMut()> = Box::into_raw(Box::new(cb));
+        let data: *mut raw::c_void = a as *mut raw::c_void;
+        let callback: Option<unsafe extern "C" fn(arg1: *mut raw::c_void)> = Some(shim);
+        match Fl_register_global_hotkey(shortcut as i32, callback, data) {
+            id if id > 0 => Ok(HotkeyHandle { id }),
+            id => {
+                let _ = Box::from_raw(a);
+                if id < 0 {
+                    Err(FltkError::Unknown(String::from(
+                        "Global hotkeys are unsupported on this platform",
+                    )))
+                } else {
+                    Err(FltkError::Internal(FltkErrorKind::FailedOperation))
+                }
+            }
+        }
     }
 }
 
@@ -488,6 +1502,32 @@ pub fn wait() -> bool {
     }
 }
 
+/// Returns whether there are events waiting to be processed, without processing them
+pub fn ready() -> bool {
+    unsafe { Fl_ready() != 0 }
+}
+
+/// Processes pending events without blocking, returning the last event dispatched or None if nothing was pending.
+/// Useful for driving the event loop under a debugger or in tests.
+/// FLTK has no way to dispatch a single event, it handles whatever the OS delivered together,
+/// so a burst of events (common on X11) is processed in one step
+pub fn step() -> Option<Event> {
+    if !ready() {
+        return None;
+    }
+    unsafe {
+        Fl_wait_for(0.0);
+    }
+    Some(event())
+}
+
+/// Returns an approximation of the number of events waiting to be processed.
+/// FLTK only reports whether any events are pending, not how many,
+/// so this is either 0 or 1 and should be treated as a lower bound
+pub fn pending_events() -> usize {
+    ready() as usize
+}
+
 /// Waits a maximum of `dur` seconds or until "something happens".
 pub fn wait_for(dur: f64) -> Result<(), FltkError> {
     unsafe {
@@ -499,98 +1539,280 @@ pub fn wait_for(dur: f64) -> Result<(), FltkError> {
     }
 }
 
+/// Waits a maximum of `dur` seconds or until "something happens", like `wait_for`,
+/// returning Ok(true) if an event was processed and Ok(false) if the wait timed out.
+/// On windows a timeout is only reported when `dur` is 0
+pub fn wait_timeout(dur: f64) -> Result<bool, FltkError> {
+    unsafe {
+        let ret = Fl_wait_for(dur);
+        if ret >= 0.0 {
+            Ok(ret > 0.0)
+        } else {
+            Err(FltkError::Unknown(String::from("An unknown error occured!")))
+        }
+    }
+}
+
+/// Caps how often the app repaints to `fps` frames per second, however many redraws are requested.
+/// Damaged windows are painted together once the next frame is due, and 0 removes the cap.
+/// The cap is a plain timer and isn't synchronized to the display's vertical refresh,
+/// so pass the monitor's refresh rate to avoid wasted frames; vsync for a `GlWindow` is still up to its swap interval.
+/// There's no separate auto-flush switch: only the flush the event loop does after handling events is capped,
+/// while explicit `flush` and `flush_and_wait` calls still paint immediately.
+/// Windows which were just shown or uncovered are painted right away instead of waiting for the next frame,
+/// along with any other damaged window, so a new window never starts out blank
+pub fn set_target_fps(fps: u32) {
+    unsafe { Fl_set_target_fps(fps) }
+}
+
+/// Draws the damaged parts of the windows now, instead of waiting for the next event loop iteration
+pub fn flush() {
+    unsafe { Fl_flush() }
+}
+
+/// Processes the pending events without blocking, then returns whether any window is still shown.
+/// Together with `wait` and `flush` this drives the event loop step by step, e.g. in tests
+pub fn check() -> bool {
+    unsafe { Fl_check() != 0 }
+}
+
+/// Flushes pending drawing and processes the resulting events until no window is damaged.
+/// Primarily useful for tests which need everything drawn before capturing the windows
+pub fn flush_and_wait() {
+    unsafe {
+        for _ in 0..100 {
+            Fl_flush();
+            Fl_check();
+            if Fl_damage() == 0 {
+                break;
+            }
+        }
+        Fl_flush();
+    }
+}
+
 /// Sends a custom message
 fn awake_msg<T>(msg: T) {
     unsafe { Fl_awake_msg(Box::into_raw(Box::from(msg)) as *mut raw::c_void) }
 }
 
-/// Receives a custom message
-fn thread_msg<T>() -> Option<T> {
+/// Receives the next custom message of the channel `id`, whose message type is identified by its `hash` and `sz`.
+/// Messages of other channels read on the way are parked until their own receiver asks for them,
+/// so every message is delivered, in the order it was sent on its channel
+fn thread_msg<T: Send + Sync>(hash: u64, sz: usize, id: u64) -> Option<T> {
+    let mut pending = PENDING_MSGS.lock().unwrap_or_else(|e| e.into_inner());
+    let mut msg = None;
+    if let Some(queue) = pending.get_mut(&id) {
+        msg = queue.pop_front().map(|m| m as *mut raw::c_void);
+        if queue.is_empty() {
+            pending.remove(&id);
+        }
+    }
     unsafe {
-        let msg = Fl_thread_msg();
-        if msg.is_null() {
-            None
-        } else {
-            let msg = Box::from_raw(msg as *const _ as *mut T);
-            Some(*msg)
+        while msg.is_none() {
+            let m = Fl_thread_msg();
+            if m.is_null() {
+                return None;
+            }
+            // Check the header before taking ownership, a message of another channel can't be read as a T
+            let header = &*(m as *const MessageHeader);
+            if header.id == id {
+                msg = Some(m);
+            } else {
+                pending.entry(header.id).or_default().push_back(m as usize);
+            }
         }
+        let msg = Box::from_raw(msg.unwrap() as *mut Message<T>);
+        debug_assert!(msg.hash == hash && msg.sz == sz);
+        Some(msg.msg)
     }
 }
 
+/// Source of the ids telling channels apart, including channels of the same message type
+static CHANNEL_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+/// Messages read by the receiver of another channel, by channel id, as pointers to their boxed `Message`
+static PENDING_MSGS: std::sync::Mutex<
+    std::collections::BTreeMap<u64, std::collections::VecDeque<usize>>,
+> = std::sync::Mutex::new(std::collections::BTreeMap::new());
+
+#[repr(C)]
+struct MessageHeader {
+    hash: u64,
+    sz: usize,
+    id: u64,
+}
+
 #[repr(C)]
-struct Message<T: Copy + Send + Sync> {
+struct Message<T: Send + Sync> {
     hash: u64,
     sz: usize,
+    id: u64,
     msg: T,
 }
 
 /// Creates a sender struct
-#[derive(Debug, Clone, Copy)]
-pub struct Sender<T: Copy + Send + Sync> {
+#[derive(Debug)]
+pub struct Sender<T: Send + Sync> {
     data: std::marker::PhantomData<T>,
     hash: u64,
     sz: usize,
+    id: u64,
+    // The type-erased conversion function of a mapped sender and the function sending its result
+    map: Option<(usize, fn(u64, usize, u64, usize, T))>,
+}
+
+impl<T: Send + Sync> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
 }
 
-impl<T: Copy + Send + Sync> Sender<T> {
-    /// Sends a message
+impl<T: Send + Sync> Copy for Sender<T> {}
+
+impl<T: Send + Sync> Sender<T> {
+    /// Sends a message, which doesn't have to be Copy as its ownership passes to the receiver
     pub fn send(&self, val: T) {
+        if let Some((f, send_mapped)) = self.map {
+            return send_mapped(self.hash, self.sz, self.id, f, val);
+        }
         let msg = Message {
             hash: self.hash,
             sz: self.sz,
+            id: self.id,
             msg: val,
         };
         awake_msg(msg)
     }
+
+    /// Creates a sender of `U` values, which are converted using `f` then sent on this sender's channel.
+    /// The receiver still receives `T` values. A mapped sender can't be mapped again
+    pub fn map<U: Send + Sync>(self, f: fn(U) -> T) -> Sender<U> {
+        assert!(self.map.is_none(), "A mapped sender can't be mapped again!");
+        fn send_mapped<U, T: Send + Sync>(hash: u64, sz: usize, id: u64, f: usize, val: U) {
+            let f: fn(U) -> T = unsafe { mem::transmute(f) };
+            let msg = Message {
+                hash,
+                sz,
+                id,
+                msg: f(val),
+            };
+            awake_msg(msg)
+        }
+        Sender {
+            data: std::marker::PhantomData,
+            hash: self.hash,
+            sz: self.sz,
+            id: self.id,
+            map: Some((f as usize, send_mapped::<U, T>)),
+        }
+    }
 }
 
 /// Creates a receiver struct
-#[derive(Debug, Clone, Copy)]
-pub struct Receiver<T: Copy + Send + Sync> {
+#[derive(Debug)]
+pub struct Receiver<T: Send + Sync> {
     data: std::marker::PhantomData<T>,
     hash: u64,
     sz: usize,
+    id: u64,
+}
+
+impl<T: Send + Sync> Clone for Receiver<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
 }
 
-impl<T: Copy + Send + Sync> Receiver<T> {
+impl<T: Send + Sync> Copy for Receiver<T> {}
+
+impl<T: Send + Sync> Receiver<T> {
     /// Receives a message
     pub fn recv(&self) -> Option<T> {
-        let data: Option<Message<T>> = thread_msg();
-        if let Some(data) = data {
-            if data.sz == self.sz && data.hash == self.hash {
-                Some(data.msg)
-            } else {
-                None
-            }
-        } else {
-            None
-        }
+        thread_msg(self.hash, self.sz, self.id)
+    }
+
+    /// Returns an iterator receiving every message delivered so far, ending once none is left.
+    /// Calling it once per event loop iteration keeps up with senders, unlike a single `recv`
+    pub fn try_iter(&self) -> impl Iterator<Item = T> {
+        let (hash, sz, id) = (self.hash, self.sz, self.id);
+        std::iter::from_fn(move || thread_msg(hash, sz, id))
     }
 }
 
-/// Creates a channel returning a Sender and Receiver structs
+/// Creates a channel returning a Sender and Receiver structs.
+/// Each call creates a distinct channel, whose receiver only receives what its own senders sent,
+/// even if other channels carry the same message type
 // The implementation could really use generic statics
-pub fn channel<T: Copy + Send + Sync>() -> (Sender<T>, Receiver<T>) {
+pub fn channel<T: Send + Sync>() -> (Sender<T>, Receiver<T>) {
     let msg_sz = std::mem::size_of::<T>();
     let type_name = std::any::type_name::<T>();
     let mut hasher = DefaultHasher::new();
     type_name.hash(&mut hasher);
     let type_hash = hasher.finish();
+    let id = CHANNEL_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
 
     let s = Sender {
         data: std::marker::PhantomData,
         hash: type_hash,
         sz: msg_sz,
+        id,
+        map: None,
     };
     let r = Receiver {
         data: std::marker::PhantomData,
         hash: type_hash,
         sz: msg_sz,
+        id,
     };
     (s, r)
 }
 
-/// Returns the first window of the application
+/// A cell holding the latest value of a frequently updated quantity, as an alternative to channels
+/// when only the latest value matters. Workers `store` without waking the event loop,
+/// and the UI reads the value with `load`, typically from a repeat_timeout running at display rate.
+/// The cell isn't lock-free: the value is guarded by a mutex which is only held while copying it in or out,
+/// so `store` and `load` never make a syscall unless they contend, and then only wait for a copy.
+/// Atomics would only cover values of up to 8 bytes without padding, while `T` can be any `Copy` type,
+/// e.g. a tuple of readings which must be updated together.
+/// Clones share the same cell
+#[derive(Debug)]
+pub struct Shared<T: Copy> {
+    inner: std::sync::Arc<std::sync::Mutex<T>>,
+}
+
+impl<T: Copy> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        Shared {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: Copy> Shared<T> {
+    /// Creates a cell holding `val`
+    pub fn new(val: T) -> Shared<T> {
+        Shared {
+            inner: std::sync::Arc::new(std::sync::Mutex::new(val)),
+        }
+    }
+
+    /// Replaces the value, without waking the event loop
+    pub fn store(&self, val: T) {
+        // A panic can't happen while the lock is held, so a poisoned value is still consistent
+        *self.inner.lock().unwrap_or_else(|e| e.into_inner()) = val;
+    }
+
+    /// Returns the latest stored value
+    pub fn load(&self) -> T {
+        *self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Returns the first window of the application, which is the most recently shown or activated one.
+/// FLTK moves a window to the front of its list whenever it's shown or gains focus, so walking the list
+/// with `next_window` goes from the topmost window downwards. FLTK doesn't query the OS's stacking order,
+/// so windows raised by the window manager alone may not be reflected until they get focus
 pub fn first_window() -> Option<Window> {
     unsafe {
         let x = Fl_first_window();
@@ -603,7 +1825,32 @@ pub fn first_window() -> Option<Window> {
     }
 }
 
-/// Returns the next window in order
+/// Returns the window grabbing all events, if any
+pub fn grab() -> Option<Window> {
+    unsafe {
+        let x = Fl_grab();
+        if x.is_null() {
+            None
+        } else {
+            Some(Window::from_widget_ptr(x as *mut fltk_sys::widget::Fl_Widget))
+        }
+    }
+}
+
+/// Makes `win` grab all events of the app, even those outside of it, as needed by popup menus.
+/// The window should be shown.
+/// Caution: Until `release` is called the rest of the UI doesn't receive any event, so forgetting it locks the app
+pub fn set_grab<W: WindowExt>(win: &W) {
+    assert!(!win.was_deleted());
+    unsafe { Fl_set_grab(win.as_widget_ptr() as *mut raw::c_void) }
+}
+
+/// Releases the grab set with `set_grab`, so events are delivered normally again
+pub fn release() {
+    unsafe { Fl_release() }
+}
+
+/// Returns the next window in order, i.e. the one below `w`, see `first_window` for the ordering
 pub fn next_window<W: WindowExt>(w: &W) -> Option<Window> {
     unsafe {
         let x = Fl_next_window(w.as_widget_ptr() as *const raw::c_void);
@@ -616,78 +1863,512 @@ pub fn next_window<W: WindowExt>(w: &W) -> Option<Window> {
     }
 }
 
-/// Quit the app
-pub fn quit() {
+/// Returns all windows ordered from the bottom to the top, the reverse of walking `first_window` and `next_window`.
+/// Useful for compositing or listing windows back to front
+pub fn windows_bottom_to_top() -> Vec<Window> {
+    let mut v = vec![];
+    let mut win = first_window();
+    while let Some(w) = win {
+        win = next_window(&w);
+        v.push(w);
+    }
+    v.reverse();
+    v
+}
+
+/// Returns the window drawing currently targets, set by `WindowExt::make_current` or while a window draws itself.
+/// In multi-window apps, check it before custom drawing outside of a draw callback
+pub fn current_window() -> Option<Window> {
     unsafe {
-        if let Some(loaded_font) = LOADED_FONT {
-            // Shouldn't fail
-            unload_font(loaded_font).unwrap_or(());
+        let x = fltk_sys::window::Fl_Window_current();
+        if x.is_null() {
+            None
+        } else {
+            Some(Window::from_widget_ptr(x as *mut fltk_sys::widget::Fl_Widget))
         }
     }
-    let mut v: Vec<Window> = vec![];
-    let first = first_window();
-    if first.is_none() {
+}
+
+/// Registers `cb` to run when the app quits, for example to flush settings which would otherwise be lost.
+/// Hooks run once, in registration order:
+/// with `quit`, before any window is hidden, and the windows' close callbacks aren't called;
+/// with `try_quit`, after the windows' close callbacks, and only if they closed every window
+pub fn on_quit(cb: Box<dyn FnMut()>) {
+    QUIT_HOOKS.with(|h| h.borrow_mut().push(cb));
+}
+
+fn dialog_pos() -> (i32, i32) {
+    unsafe { (Fl_screen_w() / 2 - 200, Fl_screen_h() / 2 - 50) }
+}
+
+/// Displays a message box centered on the screen.
+/// Use `dialog::message_title` beforehand to override its title
+pub fn message_default(msg: &str) {
+    let (x, y) = dialog_pos();
+    crate::dialog::message(x, y, msg)
+}
+
+/// Displays an alert box centered on the screen.
+/// Use `dialog::message_title` beforehand to override its title
+pub fn alert_default(msg: &str) {
+    let (x, y) = dialog_pos();
+    crate::dialog::alert(x, y, msg)
+}
+
+/// Displays a choice box centered on the screen, returning the index of the pressed button.
+/// An empty choice will not be shown.
+/// Use `dialog::message_title` beforehand to override its title
+pub fn choice_default(msg: &str, b0: &str, b1: &str, b2: &str) -> i32 {
+    let (x, y) = dialog_pos();
+    crate::dialog::choice(x, y, msg, b0, b1, b2) as i32
+}
+
+/// Displays an input box centered on the screen, returning `None` if it was cancelled.
+/// Use `dialog::message_title` beforehand to override its title
+pub fn input_default(msg: &str, default: &str) -> Option<String> {
+    let (x, y) = dialog_pos();
+    crate::dialog::input(x, y, msg, default)
+}
+
+/// Defines what `native_file_chooser` lets the user pick
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum FileChooserMode {
+    /// Existing files to open
+    Open,
+    /// A file name to save to, which may not exist yet
+    Save,
+    /// Existing directories
+    Directory,
+}
+
+/// Shows the platform's native file dialog, returning the chosen paths or `None` if it was cancelled.
+/// `pattern` follows the `FileDialog::set_filter` format and may be empty.
+/// `multiple` allows picking more than one file or directory, and is ignored in `Save` mode
+pub fn native_file_chooser(
+    title: &str,
+    pattern: &str,
+    start_dir: &str,
+    multiple: bool,
+    mode: FileChooserMode,
+) -> Option<Vec<std::path::PathBuf>> {
+    use crate::dialog::{FileDialog, FileDialogType};
+    let typ = match (mode, multiple) {
+        (FileChooserMode::Open, false) => FileDialogType::BrowseFile,
+        (FileChooserMode::Open, true) => FileDialogType::BrowseMultiFile,
+        (FileChooserMode::Save, _) => FileDialogType::BrowseSaveFile,
+        (FileChooserMode::Directory, false) => FileDialogType::BrowseDir,
+        (FileChooserMode::Directory, true) => FileDialogType::BrowseMultiDir,
+    };
+    let mut dlg = FileDialog::new(typ);
+    dlg.set_title(title);
+    if !pattern.is_empty() {
+        dlg.set_filter(pattern);
+    }
+    if !start_dir.is_empty() {
+        let _ = dlg.set_directory(std::path::Path::new(start_dir));
+    }
+    dlg.show();
+    let names = dlg.filenames();
+    if names.is_empty() {
+        None
+    } else {
+        Some(names)
+    }
+}
+
+/// Asks the user to confirm with a Yes/No dialog showing `message` whenever `quit` is called.
+/// Answering "No" vetoes the quit, leaving the windows and quit hooks untouched.
+/// Passing an empty message disables the confirmation
+pub fn set_confirm_exit(message: &str) {
+    *CONFIRM_EXIT.lock().unwrap_or_else(|e| e.into_inner()) = if message.is_empty() {
+        None
+    } else {
+        Some(message.to_owned())
+    };
+}
+
+/// Quit the app, hiding all windows without calling their close callbacks
+pub fn quit() {
+    if !confirm_exit() {
         return;
     }
-    let first = first.unwrap();
-    v.push(first.clone());
-    let mut win = first;
-    while let Some(wind) = next_window(&win) {
-        v.push(wind.clone());
-        win = wind;
-    }
-    for mut i in v {
+    run_quit_hooks();
+    for mut i in windows() {
         if i.shown() {
             i.hide();
         }
     }
 }
 
-/// Adds a one-shot timeout callback. The timeout duration `tm` is indicated in seconds
-pub fn add_timeout(tm: f64, cb: Box<dyn FnMut()>) {
-    unsafe {
-        unsafe extern "C" fn shim(data: *mut raw::c_void) {
-            let a: *mut Box<dyn FnMut()> = data as *mut Box<dyn FnMut()>;
-            let f: &mut (dyn FnMut()) = &mut **a;
-            let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f()));
+/// Quits the app the way the user closing every window would: after the `set_confirm_exit` confirmation,
+/// the close callback of each shown window is called, and close callbacks may keep their window open.
+/// The `on_quit` hooks then run if no window is left shown.
+/// Returns whether the app quit
+pub fn try_quit() -> bool {
+    confirm_exit() && close_windows(windows())
+}
+
+fn confirm_exit() -> bool {
+    // Not locked while the dialog runs the event loop
+    let message = CONFIRM_EXIT.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    match message {
+        Some(message) => choice_default(&message, "No", "Yes", "") == 1,
+        None => true,
+    }
+}
+
+fn run_quit_hooks() {
+    for mut cb in QUIT_HOOKS.with(|h| mem::take(&mut *h.borrow_mut())) {
+        catch_callback_panic(|| cb());
+    }
+    let loaded = mem::take(&mut *LOADED_FONTS.lock().unwrap_or_else(|e| e.into_inner()));
+    for (loaded_font, idx) in loaded {
+        unload_font_file(&loaded_font, idx);
+    }
+}
+
+/// Calls the close callbacks of the shown windows among `wins`, then runs the quit hooks if they all closed
+fn close_windows(wins: Vec<Window>) -> bool {
+    for mut w in wins.iter().cloned() {
+        if !w.was_deleted() && w.shown() {
+            w.do_callback();
         }
+    }
+    let closed = wins.iter().all(|w| w.was_deleted() || !w.shown());
+    if closed {
+        run_quit_hooks();
+    }
+    closed
+}
+
+fn windows() -> Vec<Window> {
+    let mut v: Vec<Window> = vec![];
+    let mut win = first_window();
+    while let Some(w) = win {
+        win = next_window(&w);
+        v.push(w);
+    }
+    v
+}
+
+unsafe extern "C" fn timeout_shim(data: *mut raw::c_void) {
+    let a: *mut Box<dyn FnMut()> = data as *mut Box<dyn FnMut()>;
+    let f: &mut (dyn FnMut()) = &mut **a;
+    catch_callback_panic(|| f());
+}
+
+/// Handle to a timeout added with `add_timeout` or `repeat_timeout`, used to repeat or cancel it
+#[derive(Debug, Copy, Clone)]
+pub struct TimeoutHandle {
+    data: *mut raw::c_void,
+    shim: unsafe extern "C" fn(*mut raw::c_void),
+}
+
+impl TimeoutHandle {
+    fn new(cb: Box<dyn FnMut()>) -> TimeoutHandle {
         let a: *mut Box<dyn FnMut()> = Box::into_raw(Box::new(cb));
-        let data: *mut raw::c_void = a as *mut raw::c_void;
-        let callback: Option<unsafe extern "C" fn(arg1: *mut raw::c_void)> = Some(shim);
-        fltk_sys::fl::Fl_add_timeout(tm, callback, data);
+        TimeoutHandle {
+            data: a as *mut raw::c_void,
+            shim: timeout_shim,
+        }
+    }
+
+    /// Schedules the same callback again `tm` seconds after the expiration of the current timeout,
+    /// so the handle stays valid for the whole chain of repeats.
+    /// You may only call this method inside a timeout callback
+    pub fn repeat(&self, tm: f64) {
+        unsafe { Fl_repeat_timeout(tm, Some(self.shim), self.data) }
+    }
+
+    /// Returns whether the timeout is still pending
+    pub fn is_pending(&self) -> bool {
+        unsafe { Fl_has_timeout(Some(self.shim), self.data) != 0 }
+    }
+
+    /// Cancels the timeout if it's still pending, so its callback won't be called.
+    /// Don't call it from within the timeout's own callback after repeating it, stop repeating instead
+    pub fn remove(self) {
+        unsafe {
+            if self.is_pending() {
+                Fl_remove_timeout(Some(self.shim), self.data);
+                let _ = Box::from_raw(self.data as *mut Box<dyn FnMut()>);
+            }
+        }
     }
 }
 
+/// Adds a one-shot timeout callback. The timeout duration `tm` is indicated in seconds.
+/// Returns a handle which can cancel the timeout
+pub fn add_timeout(tm: f64, cb: Box<dyn FnMut()>) -> TimeoutHandle {
+    let handle = TimeoutHandle::new(cb);
+    unsafe { Fl_add_timeout(tm, Some(handle.shim), handle.data) }
+    handle
+}
+
+/// Adds a one-shot timeout callback, with the duration `ms` indicated in milliseconds
+pub fn add_timeout_ms(ms: u64, cb: Box<dyn FnMut()>) -> TimeoutHandle {
+    add_timeout(ms as f64 / 1000.0, cb)
+}
+
 /// Repeats a timeout callback from the expiration of the previous timeout
 /// You may only call this method inside a timeout callback.
-/// The timeout duration `tm` is indicated in seconds
-pub fn repeat_timeout(tm: f64, cb: Box<dyn FnMut()>) {
+/// The timeout duration `tm` is indicated in seconds.
+/// To keep a single handle for every repeat, use `TimeoutHandle::repeat` instead
+pub fn repeat_timeout(tm: f64, cb: Box<dyn FnMut()>) -> TimeoutHandle {
+    let handle = TimeoutHandle::new(cb);
+    unsafe { Fl_repeat_timeout(tm, Some(handle.shim), handle.data) }
+    handle
+}
+
+/// Repeats a timeout callback from the expiration of the previous timeout, with the duration `ms` indicated in milliseconds.
+/// You may only call this method inside a timeout callback
+pub fn repeat_timeout_ms(ms: u64, cb: Box<dyn FnMut()>) -> TimeoutHandle {
+    repeat_timeout(ms as f64 / 1000.0, cb)
+}
+
+/// Removes a timeout callback.
+/// A new box never matches the callback of an existing timeout, use `TimeoutHandle::remove` instead
+pub fn remove_timeout(cb: Box<dyn FnMut()>) {
     unsafe {
         unsafe extern "C" fn shim(data: *mut raw::c_void) {
             let a: *mut Box<dyn FnMut()> = data as *mut Box<dyn FnMut()>;
             let f: &mut (dyn FnMut()) = &mut **a;
-            let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f()));
+            catch_callback_panic(|| f());
         }
         let a: *mut Box<dyn FnMut()> = Box::into_raw(Box::new(cb));
         let data: *mut raw::c_void = a as *mut raw::c_void;
         let callback: Option<unsafe extern "C" fn(arg1: *mut raw::c_void)> = Some(shim);
-        fltk_sys::fl::Fl_repeat_timeout(tm, callback, data);
+        fltk_sys::fl::Fl_remove_timeout(callback, data);
     }
 }
 
-/// Removes a timeout callback
-pub fn remove_timeout(cb: Box<dyn FnMut()>) {
+/// Handle to an idle callback added with `add_idle`, used to remove it
+#[derive(Debug, Copy, Clone)]
+pub struct IdleHandle {
+    data: *mut raw::c_void,
+}
+
+impl IdleHandle {
+    /// Returns whether the idle callback is still installed
+    pub fn is_active(&self) -> bool {
+        unsafe { Fl_has_idle(Some(timeout_shim), self.data) != 0 }
+    }
+}
+
+/// Adds an idle callback, which runs repeatedly whenever the event loop has no events to handle, until removed.
+/// Useful for incremental background work, but it keeps a core busy as long as it's installed,
+/// see `add_idle_throttled` for a rate-limited version.
+/// Returns a handle to pass to `remove_idle`
+pub fn add_idle(cb: Box<dyn FnMut()>) -> IdleHandle {
+    let a: *mut Box<dyn FnMut()> = Box::into_raw(Box::new(cb));
+    let handle = IdleHandle {
+        data: a as *mut raw::c_void,
+    };
+    unsafe { Fl_add_idle(Some(timeout_shim), handle.data) }
+    handle
+}
+
+/// Removes an idle callback added with `add_idle`, dropping its closure.
+/// Don't call it from within the idle callback itself
+pub fn remove_idle(handle: IdleHandle) {
+    unsafe {
+        if handle.is_active() {
+            Fl_remove_idle(Some(timeout_shim), handle.data);
+            let _ = Box::from_raw(handle.data as *mut Box<dyn FnMut()>);
+        }
+    }
+}
+
+/// Handle to a check callback added with `add_check`, used to remove it
+#[derive(Debug, Copy, Clone)]
+pub struct CheckHandle {
+    data: *mut raw::c_void,
+}
+
+impl CheckHandle {
+    /// Returns whether the check callback is still installed
+    pub fn is_active(&self) -> bool {
+        unsafe { Fl_has_check(Some(timeout_shim), self.data) != 0 }
+    }
+}
+
+/// Adds a check callback, which runs every time the event loop is about to wait for events, until removed.
+/// Unlike idle callbacks, which run repeatedly while no events are pending, checks run once per loop iteration
+/// after the events are handled, which makes them the place to poll file descriptors or async runtimes.
+/// Returns a handle to pass to `remove_check`
+pub fn add_check(cb: Box<dyn FnMut()>) -> CheckHandle {
+    let a: *mut Box<dyn FnMut()> = Box::into_raw(Box::new(cb));
+    let handle = CheckHandle {
+        data: a as *mut raw::c_void,
+    };
+    unsafe { Fl_add_check(Some(timeout_shim), handle.data) }
+    handle
+}
+
+/// Removes a check callback added with `add_check`, dropping its closure.
+/// Don't call it from within the check callback itself
+pub fn remove_check(handle: CheckHandle) {
+    unsafe {
+        if handle.is_active() {
+            Fl_remove_check(Some(timeout_shim), handle.data);
+            let _ = Box::from_raw(handle.data as *mut Box<dyn FnMut()>);
+        }
+    }
+}
+
+/// Feeds the items of `iter` to `per_batch`, `batch` items at a time, during the idle time of the event loop.
+/// Useful for populating widgets with large datasets while keeping the app responsive.
+/// The idle callback is removed once the iterator is exhausted
+pub fn populate_incrementally<I, F>(iter: I, batch: usize, mut per_batch: F)
+where
+    I: Iterator + 'static,
+    F: FnMut(&[I::Item]) + 'static,
+{
+    assert!(batch > 0);
+    let mut iter = iter;
+    let cb: Box<dyn FnMut() -> bool> = Box::new(move || {
+        let items: Vec<I::Item> = iter.by_ref().take(batch).collect();
+        if !items.is_empty() {
+            per_batch(&items);
+        }
+        items.len() == batch
+    });
     unsafe {
         unsafe extern "C" fn shim(data: *mut raw::c_void) {
-            let a: *mut Box<dyn FnMut()> = data as *mut Box<dyn FnMut()>;
-            let f: &mut (dyn FnMut()) = &mut **a;
-            let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f()));
+            let a: *mut Box<dyn FnMut() -> bool> = data as *mut Box<dyn FnMut() -> bool>;
+            let f: &mut (dyn FnMut() -> bool) = &mut **a;
+            let more = catch_callback_panic(|| f());
+            if !more.unwrap_or(false) {
+                Fl_remove_idle(Some(shim), data);
+                let _ = Box::from_raw(a);
+            }
         }
-        let a: *mut Box<dyn FnMut()> = Box::into_raw(Box::new(cb));
+        let a: *mut Box<dyn FnMut() -> bool> = Box::into_raw(Box::new(cb));
         let data: *mut raw::c_void = a as *mut raw::c_void;
         let callback: Option<unsafe extern "C" fn(arg1: *mut raw::c_void)> = Some(shim);
-        fltk_sys::fl::Fl_remove_timeout(callback, data);
+        Fl_add_idle(callback, data);
+    }
+}
+
+/// Runs `cb` during the idle time of the event loop, but at most once every `min_interval`.
+/// Between runs the idle callback sleeps for the remaining time, capped at 10ms so events stay responsive,
+/// instead of spinning a core like a plain idle callback would.
+/// Unlike a timeout, the work only runs when no events are pending,
+/// but it also adds up to 10ms of latency to events arriving while idle.
+/// Returns a handle to pass to `remove_idle`
+pub fn add_idle_throttled(min_interval: std::time::Duration, cb: Box<dyn FnMut()>) -> IdleHandle {
+    let mut cb = cb;
+    let mut last: Option<std::time::Instant> = None;
+    let idle: Box<dyn FnMut()> = Box::new(move || {
+        let elapsed = last.map(|t| t.elapsed());
+        match elapsed {
+            Some(elapsed) if elapsed < min_interval => {
+                let remaining = min_interval - elapsed;
+                std::thread::sleep(remaining.min(std::time::Duration::from_millis(10)));
+            }
+            _ => {
+                last = Some(std::time::Instant::now());
+                cb();
+            }
+        }
+    });
+    add_idle(idle)
+}
+
+/// Calls `cb` with the new width and height of `win` once it stops being resized for `delay`.
+/// Useful for doing expensive relayouts once instead of on every intermediate size.
+/// Every resize restarts a single timeout, so nothing runs while the window keeps its size.
+/// This sets the window's `resize_callback`, replacing any previous one
+pub fn on_resize_settled<W: WindowExt>(
+    win: &mut W,
+    delay: std::time::Duration,
+    mut cb: Box<dyn FnMut(i32, i32)>,
+) {
+    assert!(!win.was_deleted());
+    let wid = unsafe { crate::widget::Widget::from_widget_ptr(win.as_widget_ptr()) };
+    let mut last = (wid.width(), wid.height());
+    let settle = TimeoutHandle::new(Box::new(move || {
+        if !wid.was_deleted() {
+            cb(wid.width(), wid.height());
+        }
+    }));
+    let tm = delay.as_secs_f64();
+    win.resize_callback(Box::new(move |_, _, w, h| {
+        if (w, h) != last {
+            last = (w, h);
+            unsafe {
+                Fl_remove_timeout(Some(settle.shim), settle.data);
+                Fl_add_timeout(tm, Some(settle.shim), settle.data);
+            }
+        }
+    }));
+}
+
+/// Recursively visits every widget inside `win` along with its depth, direct children having a depth of 0.
+/// The walk stops early once `visit` returns false
+pub fn walk_widgets<W: WindowExt, F: FnMut(&crate::widget::Widget, usize) -> bool>(
+    win: &W,
+    mut visit: F,
+) {
+    fn walk<G: GroupExt>(
+        grp: &G,
+        depth: usize,
+        visit: &mut dyn FnMut(&crate::widget::Widget, usize) -> bool,
+    ) -> bool {
+        for i in 0..grp.children() {
+            if let Some(mut child) = grp.child(i) {
+                if !visit(&child, depth) {
+                    return false;
+                }
+                if let Some(sub) = child.as_group() {
+                    if !walk(&sub, depth + 1, visit) {
+                        return false;
+                    }
+                }
+            }
+        }
+        true
+    }
+    assert!(!win.was_deleted());
+    walk(win, 0, &mut visit);
+}
+
+fn find_widget_by<F: Fn(&str) -> bool>(matches: F) -> Option<crate::widget::Widget> {
+    let mut found = None;
+    let mut win = first_window();
+    while let Some(w) = win {
+        walk_widgets(&w, |wid, _| {
+            if matches(&wid.label()) {
+                found = Some(wid.clone());
+            }
+            found.is_none()
+        });
+        if found.is_some() {
+            break;
+        }
+        win = next_window(&w);
     }
+    found
+}
+
+/// Finds the first widget, across all shown windows, whose label is exactly `label`
+pub fn find_widget(label: &str) -> Option<crate::widget::Widget> {
+    find_widget_by(|l| l == label)
+}
+
+/// Finds the first widget, across all shown windows, whose label contains `label`
+pub fn find_widget_containing(label: &str) -> Option<crate::widget::Widget> {
+    find_widget_by(|l| l.contains(label))
+}
+
+/// Sets the exit code returned by App::run_with_exit_code
+pub fn set_exit_code(code: i32) {
+    EXIT_CODE.store(code, std::sync::atomic::Ordering::SeqCst);
+}
+
+/// Gets the exit code set using app::set_exit_code
+pub fn exit_code() -> i32 {
+    EXIT_CODE.load(std::sync::atomic::Ordering::SeqCst)
 }
 
 /// Returns whether a quit signal was sent
@@ -705,6 +2386,21 @@ pub fn program_should_quit(flag: bool) {
     unsafe { Fl_program_should_quit(flag as i32) }
 }
 
+/// Returns the event position clamped to the bounds of `w`, in the same window coordinates as `event_x` and `event_y`.
+/// The widget's absolute rect on the screen is compared to the event's root position, so this is correct for widgets
+/// inside subwindows and for windows themselves. The result lies within `x..x + w` and `y..y + h`, excluding the far edges,
+/// so it can be used for indexing during drags leaving the widget
+pub fn event_coords_clamped<W: WidgetExt>(w: &W) -> (i32, i32) {
+    assert!(!w.was_deleted());
+    let (sx, sy, sw, sh) = w.screen_xywh();
+    let (rx, ry) = (event_x_root(), event_y_root());
+    // Screen position of the window the event coordinates are relative to
+    let (ox, oy) = (rx - event_x(), ry - event_y());
+    let cx = std::cmp::max(sx, std::cmp::min(rx, sx + sw - 1));
+    let cy = std::cmp::max(sy, std::cmp::min(ry, sy + sh - 1));
+    (cx - ox, cy - oy)
+}
+
 /// Returns whether an event occured within a widget
 pub fn event_inside_widget<Wid: WidgetExt>(wid: &Wid) -> bool {
     assert!(!wid.was_deleted());
@@ -730,14 +2426,15 @@ pub fn event_inside(x: i32, y: i32, w: i32, h: i32) -> bool {
     }
 }
 
-/// Gets the widget that is below the mouse cursor
-pub fn belowmouse<Wid: WidgetExt>() -> Option<impl WidgetExt> {
+/// Gets the widget that is below the mouse cursor.
+/// Use `Widget::into` to convert it to its concrete type
+pub fn belowmouse() -> Option<crate::widget::Widget> {
     unsafe {
         let x = Fl_belowmouse() as *mut fltk_sys::fl::Fl_Widget;
         if x.is_null() {
             None
         } else {
-            Some(crate::widget::Widget::from_widget_ptr(
+            Some(crate::widget::Widget::from_raw(
                 x as *mut fltk_sys::widget::Fl_Widget,
             ))
         }
@@ -798,6 +2495,21 @@ pub fn is_event_alt() -> bool {
     unsafe { Fl_event_alt() != 0 }
 }
 
+/// Returns whether caps lock is on, reported on all platforms
+pub fn is_caps_lock() -> bool {
+    unsafe { Fl_event_state() & 0x0002_0000 != 0 }
+}
+
+/// Returns whether num lock is on, reported on Windows and X11
+pub fn is_num_lock() -> bool {
+    unsafe { Fl_event_state() & 0x0010_0000 != 0 }
+}
+
+/// Returns whether scroll lock is on, reported on Windows and X11
+pub fn is_scroll_lock() -> bool {
+    unsafe { Fl_event_state() & 0x0080_0000 != 0 }
+}
+
 /// Sets the damage to true or false, illiciting a redraw by the application
 pub fn set_damage(flag: bool) {
     unsafe { Fl_set_damage(flag as i32) }
@@ -808,16 +2520,83 @@ pub fn damage() -> bool {
     unsafe { Fl_damage() != 0 }
 }
 
-/// Sets the visual mode of the application
+/// Sets the visual mode of the application.
+/// Modes can be combined, e.g. `Mode::Rgb | Mode::Double | Mode::Depth`, and are passed to FLTK as one bitmask
 pub fn set_visual(mode: Mode) -> Result<(), FltkError> {
     unsafe {
-        match Fl_visual(mode as i32) {
+        match Fl_visual(mode.bits()) {
             0 => Err(FltkError::Internal(FltkErrorKind::FailedOperation)),
             _ => Ok(()),
         }
     }
 }
 
+/// Overrides the focus box drawn around the focused widget with a ring of `color`, dashed or solid.
+/// Applies to all widgets with visible focus
+pub fn set_focus_ring(color: Color, dashed: bool) {
+    unsafe { Fl_set_focus_ring(color as u32, dashed as i32) }
+}
+
+/// Sets a callback drawn on top of every top-level window, after the normal widget painting.
+/// Drawing is done with the `draw` module in window coordinates, and `get_mouse` gives the current mouse position.
+/// To follow the mouse, call `redraw` on mouse moves
+pub fn set_overlay_draw(cb: Box<dyn FnMut()>) {
+    unsafe {
+        unsafe extern "C" fn shim(data: *mut raw::c_void) {
+            let a: *mut Box<dyn FnMut()> = data as *mut Box<dyn FnMut()>;
+            let f: &mut (dyn FnMut()) = &mut **a;
+            catch_callback_panic(|| f());
+        }
+        let old = Fl_overlay_draw_data();
+        let a: *mut Box<dyn FnMut()> = Box::into_raw(Box::new(cb));
+        let data: *mut raw::c_void = a as *mut raw::c_void;
+        let callback: Option<unsafe extern "C" fn(arg1: *mut raw::c_void)> = Some(shim);
+        Fl_set_overlay_draw(callback, data);
+        if !old.is_null() {
+            let _ = Box::from_raw(old as *mut Box<dyn FnMut()>);
+        }
+    }
+    redraw();
+}
+
+/// Sets the delay in seconds before a tooltip appears, same as `misc::Tooltip::set_delay`
+pub fn set_tooltip_delay(seconds: f32) {
+    crate::misc::Tooltip::set_delay(seconds)
+}
+
+/// Gets the delay in seconds before a tooltip appears
+pub fn tooltip_delay() -> f32 {
+    crate::misc::Tooltip::delay()
+}
+
+/// Sets the background color of all tooltips, same as `misc::Tooltip::set_color`
+pub fn set_tooltip_color(c: Color) {
+    crate::misc::Tooltip::set_color(c)
+}
+
+/// Sets the text color of all tooltips, same as `misc::Tooltip::set_text_color`
+pub fn set_tooltip_text_color(c: Color) {
+    crate::misc::Tooltip::set_text_color(c)
+}
+
+/// Sets the delay in seconds before another tooltip appears when moving between widgets while one is shown,
+/// same as `misc::Tooltip::set_hoverdelay`
+pub fn set_tooltip_hover_delay(seconds: f32) {
+    crate::misc::Tooltip::set_hoverdelay(seconds)
+}
+
+/// Sets the width in pixels at which tooltip text is wrapped, same as `misc::Tooltip::set_wrap_width`
+pub fn set_tooltip_wrap_width(px: i32) {
+    crate::misc::Tooltip::set_wrap_width(std::cmp::max(px, 0) as u32)
+}
+
+/// Returns whether a visual mode can be granted, without setting it.
+/// Useful for falling back gracefully, for example when multisampling isn't available.
+/// Without OpenGL support, only the basic color and double-buffering modes are reported as available
+pub fn can_do_visual(mode: Mode) -> bool {
+    unsafe { Fl_can_do_visual(mode.bits()) != 0 }
+}
+
 /// Makes FLTK use its own colormap. This may make FLTK display better
 pub fn own_colormap() {
     unsafe { Fl_own_colormap() }
@@ -856,15 +2635,83 @@ pub fn set_focus<W: WidgetExt>(wid: &W) {
     unsafe { Fl_set_focus(wid.as_widget_ptr() as *mut raw::c_void) }
 }
 
-/// Delays the current thread by millis. Because std::thread::sleep isn't accurate on windows!
-/// Caution: It's a busy wait!
+thread_local! {
+    static DEFAULT_ICON: std::cell::RefCell<Option<crate::image::Image>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+/// Sets the icon of all windows which don't set their own with `WindowExt::set_icon`,
+/// including windows created afterwards. A copy of the image is kept alive for as long as it's the default icon.
+/// FLTK only takes RGB images as icons, other formats can be converted with `ImageExt::to_rgb`
+pub fn set_default_icon(image: &crate::image::RgbImage) {
+    assert!(!image.was_deleted());
+    let icon = image.copy();
+    unsafe {
+        fltk_sys::window::Fl_Window_set_default_icon(icon.as_ptr());
+        let icon = crate::image::Image::from_raw(icon.as_image_ptr());
+        if let Some(mut old) = DEFAULT_ICON.with(|i| i.borrow_mut().replace(icon)) {
+            old.delete();
+        }
+    }
+}
+
+thread_local! {
+    static FOCUS_STACK: std::cell::RefCell<Vec<crate::widget::Widget>> =
+        const { std::cell::RefCell::new(Vec::new()) };
+}
+
+/// Remembers `wid` so that `pop_focus` can give it the focus back, e.g. before showing a dialog.
+/// Pushing doesn't change the focus
+pub fn push_focus<W: WidgetExt>(wid: &W) {
+    assert!(!wid.was_deleted());
+    let wid = unsafe { crate::widget::Widget::from_raw(wid.as_widget_ptr()) };
+    FOCUS_STACK.with(|s| s.borrow_mut().push(wid));
+}
+
+/// Gives the focus back to the widget last remembered with `push_focus`, skipping widgets deleted since.
+/// Returns whether a widget got the focus
+pub fn pop_focus() -> bool {
+    while let Some(mut wid) = FOCUS_STACK.with(|s| s.borrow_mut().pop()) {
+        let alive = !wid.was_deleted();
+        if alive {
+            set_focus(&wid);
+        }
+        unsafe { wid.cleanup() }
+        if alive {
+            return true;
+        }
+    }
+    false
+}
+
+/// Makes widgets gain focus when hovered instead of when clicked, app-wide.
+/// Only widgets accepting focus are affected. Note that text inputs then receive keyboard input
+/// and may lose their selection as soon as the mouse leaves them.
+/// It doesn't replace an interceptor set with `set_event_dispatch`,
+/// and focus follows the mouse even for move events the interceptor swallows
+pub fn set_focus_follows_mouse(enabled: bool) {
+    unsafe { Fl_set_focus_follows_mouse(enabled as i32) }
+}
+
+/// Delays the current thread by millis, more accurately than std::thread::sleep on windows.
+/// The thread sleeps for most of the delay and only spins for the last slice,
+/// which is shorter than the timer resolution of the platform
 pub fn delay(millis: u128) {
+    // Sleeps can overshoot by up to the timer resolution, which is about 15.6ms by default on windows
+    let resolution = if cfg!(target_os = "windows") { 16 } else { 1 };
+    let target = std::time::Duration::from_millis(millis as u64);
     let now = std::time::Instant::now();
     loop {
-        let after = std::time::Instant::now();
-        if after.duration_since(now).as_millis() > millis {
+        let elapsed = now.elapsed();
+        if elapsed >= target {
             break;
         }
+        let remaining = target - elapsed;
+        if remaining.as_millis() > resolution {
+            std::thread::sleep(remaining - std::time::Duration::from_millis(resolution as u64));
+        } else {
+            std::thread::yield_now();
+        }
     }
 }
 
@@ -883,6 +2730,18 @@ pub fn abi_version() -> i32 {
     unsafe { Fl_abi_version() }
 }
 
+/// Gets the FLTK major, minor and patch versions
+pub fn version_parts() -> (i32, i32, i32) {
+    let v = api_version();
+    (v / 10000, (v / 100) % 100, v % 100)
+}
+
+/// Gets the human-readable FLTK version, e.g. "1.4.0"
+pub fn version_string() -> String {
+    let (major, minor, patch) = version_parts();
+    format!("{}.{}.{}", major, minor, patch)
+}
+
 /// Gets FLTK crate version
 pub fn crate_version() -> &'static str {
     env!("CARGO_PKG_VERSION")
@@ -905,8 +2764,19 @@ pub fn graphics_context() -> GraphicsContext {
 /// _XDisplay on X11, HINSTANCE on Windows. 
 pub type Display = *mut raw::c_void;
 
+/// Opens the connection to the display, it's done automatically when the first window is shown
+pub fn open_display() {
+    unsafe { fltk_sys::window::Fl_open_display() }
+}
+
+/// Closes the connection to the display, windows shouldn't be shown afterwards
+pub fn close_display() {
+    unsafe { fltk_sys::window::Fl_close_display() }
+}
+
 /// Gets the display global variable, fl_display
 /// _XDisplay on X11, HINSTANCE on Windows.
+/// Panics if the display isn't open yet, see `open_display`
 pub fn display() -> Display {
     unsafe {
         let disp = fltk_sys::window::Fl_display();
@@ -922,37 +2792,768 @@ pub fn dnd() {
     }
 }
 
-/// Load a font from a file
-fn load_font(path: &str) -> Result<String, FltkError> {
+/// Loads a font from a path.
+/// On success, returns the assigned Font along with the ttf Font Family name.
+/// Each loaded font gets the next free index from 16 on, so several fonts can be used at once.
+/// Loading an already loaded path returns its existing Font.
+/// # Examples
+/// ```
+/// use fltk::*;
+/// let app = app::App::default();
+/// let (font, name) = app::load_font(&std::path::Path::new("font.ttf")).unwrap();
+/// let mut frame = frame::Frame::new(0, 0, 400, 100, &name);
+/// frame.set_label_font(font);
+/// ```
+pub fn load_font(path: &std::path::Path) -> Result<(Font, String), FltkError> {
+    if !path.exists() {
+        return Err::<(Font, String), FltkError>(FltkError::Internal(FltkErrorKind::ResourceNotFound));
+    }
+    let path = match path.to_str() {
+        Some(p) => p,
+        None => return Err(FltkError::Internal(FltkErrorKind::ResourceNotFound)),
+    };
+    let mut loaded = LOADED_FONTS.lock().unwrap_or_else(|e| e.into_inner());
     unsafe {
-        let path = CString::new(path)?;
-        if let Some(load_font) = LOADED_FONT {
-            unload_font(load_font)?;
+        if let Some((_, idx)) = loaded.iter().find(|(p, _)| p == path) {
+            return Ok((Font::by_index(*idx), FONTS[*idx].clone()));
         }
-        let ptr = Fl_load_font(path.as_ptr());
+        // Reuse the slot of an unloaded font if there's one
+        let idx = (16..FONTS.len())
+            .find(|i| FONTS[*i].is_empty())
+            .unwrap_or_else(|| std::cmp::max(FONTS.len(), 16));
+        let cpath = CString::new(path)?;
+        let ptr = Fl_load_font(cpath.as_ptr(), idx as i32);
         if ptr.is_null() {
-            Err::<String, FltkError>(FltkError::Internal(FltkErrorKind::FailedOperation))
+            Err::<(Font, String), FltkError>(FltkError::Internal(FltkErrorKind::FailedOperation))
         } else {
-            let name = CString::from_raw(ptr as *mut _).to_string_lossy().to_string();
-            if FONTS.len() < 17 {
-                FONTS.push(name.clone());
+            let name = CStr::from_ptr(ptr).to_string_lossy().to_string();
+            if idx < FONTS.len() {
+                FONTS[idx] = name.clone();
             } else {
-                FONTS[16] = name.clone();
+                FONTS.resize(idx, String::new());
+                FONTS.push(name.clone());
+            }
+            loaded.push((path.to_owned(), idx));
+            Ok((Font::by_index(idx), name))
+        }
+    }
+}
+
+/// Unloads a font previously loaded with `load_font`, freeing it.
+/// Its name no longer resolves through `font_index` or `Font::by_name`, and its index may be reused by the next loaded font.
+/// Widgets still using the font fall back to another one.
+/// Returns ResourceNotFound if the path wasn't loaded
+pub fn unload_font(path: &std::path::Path) -> Result<(), FltkError> {
+    let path = path
+        .to_str()
+        .ok_or(FltkError::Internal(FltkErrorKind::ResourceNotFound))?;
+    let mut loaded = LOADED_FONTS.lock().unwrap_or_else(|e| e.into_inner());
+    match loaded.iter().position(|(p, _)| p == path) {
+        Some(pos) => {
+            let (path, idx) = loaded.remove(pos);
+            unload_font_file(&path, idx);
+            // On X11 unloading drops every loaded font, so the others are registered again
+            for (other, _) in loaded.iter() {
+                if let Ok(other) = CString::new(other.as_str()) {
+                    unsafe { Fl_reload_font(other.as_ptr()) }
+                }
             }
-            Ok(name)
+            Ok(())
         }
+        None => Err(FltkError::Internal(FltkErrorKind::ResourceNotFound)),
     }
 }
 
-/// Unload a loaded font
-fn unload_font(path: &str) -> Result<(), FltkError> {
+fn unload_font_file(path: &str, idx: usize) {
     unsafe {
-        let check = std::path::PathBuf::from(path);
-        if !check.exists() {
-            return Err::<(), FltkError>(FltkError::Internal(FltkErrorKind::ResourceNotFound));
+        if let Ok(path) = CString::new(path) {
+            Fl_unload_font(path.as_ptr());
         }
-        let path = CString::new(path)?;
-        Fl_unload_font(path.as_ptr());
-        Ok(())
+        if idx < FONTS.len() {
+            FONTS[idx].clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod app {
+    use super::*;
+    fn fonts() -> Vec<String> {
+        ["Helvetica", "HelveticaBold", "Courier", "CourierBold", "DejaVu Sans"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect()
+    }
+    #[test]
+    fn font_casing() {
+        assert_eq!(closest_font(&fonts(), "helvetica"), Some((0, true)));
+        assert_eq!(closest_font(&fonts(), "COURIERBOLD"), Some((3, true)));
+        assert_eq!(closest_font(&fonts(), "Courier Bold"), Some((3, false)));
+    }
+    #[test]
+    fn sender_map() {
+        #[derive(Debug, Copy, Clone, PartialEq)]
+        #[repr(u32)]
+        enum Msg {
+            Open = 1,
+            Save = 2,
+        }
+        let (s, r) = channel::<u32>();
+        let mapped = s.map(|m: Msg| m as u32);
+        assert_eq!((mapped.hash, mapped.sz, mapped.id), (r.hash, r.sz, r.id));
+        let (f, _) = mapped.map.unwrap();
+        let f: fn(Msg) -> u32 = unsafe { mem::transmute(f) };
+        assert_eq!(f(Msg::Open), 1);
+        assert_eq!(f(Msg::Save), 2);
+    }
+    #[test]
+    fn dropped_files() {
+        use std::path::PathBuf;
+        let text = "file:///home/me/My%20Notes.txt\r\nfile://localhost/tmp/a%2\nhttp://example.com/x\n/plain/path\n";
+        assert_eq!(
+            parse_dropped_files(text),
+            vec![
+                PathBuf::from("/home/me/My Notes.txt"),
+                PathBuf::from("/tmp/a%2"),
+                PathBuf::from("/plain/path"),
+            ]
+        );
+    }
+    #[test]
+    fn shared_latest_value() {
+        let cell = Shared::new((0u64, 0u64));
+        let writer = cell.clone();
+        let t = std::thread::spawn(move || {
+            for i in 1..=10_000u64 {
+                writer.store((i, i * 2));
+            }
+        });
+        for _ in 0..10_000 {
+            let (a, b) = cell.load();
+            assert_eq!(b, a * 2);
+        }
+        t.join().unwrap();
+        assert_eq!(cell.load(), (10_000, 20_000));
+    }
+    #[test]
+    fn scheme_oxy() {
+        set_scheme(Scheme::Oxy);
+        assert_eq!(scheme(), Scheme::Oxy);
+        set_scheme(Scheme::Base);
+        assert_eq!(scheme(), Scheme::Base);
+    }
+    #[test]
+    fn scheme_cycle_order() {
+        let mut s = Scheme::Gleam;
+        let mut seen = vec![];
+        for _ in 0..5 {
+            s = next_scheme(s);
+            seen.push(s);
+        }
+        assert_eq!(
+            seen,
+            vec![Scheme::Oxy, Scheme::Base, Scheme::Plastic, Scheme::Gtk, Scheme::Gleam]
+        );
+    }
+    #[test]
+    fn scheme_by_name() {
+        assert!(set_scheme_str("gleam").is_ok());
+        assert_eq!(scheme(), Scheme::Gleam);
+        assert!(set_scheme_str("no such scheme").is_err());
+        assert_eq!(scheme(), Scheme::Gleam);
+        assert!(set_scheme_str("base").is_ok());
+        assert_eq!(scheme(), Scheme::Base);
+    }
+    #[test]
+    fn timeout_remove() {
+        let fired = std::rc::Rc::new(std::cell::Cell::new(false));
+        let f = fired.clone();
+        let handle = add_timeout(10.0, Box::new(move || f.set(true)));
+        assert!(handle.is_pending());
+        handle.remove();
+        assert!(!handle.is_pending());
+        for _ in 0..10 {
+            let _ = wait_for(0.01);
+        }
+        assert!(!fired.get());
+    }
+    #[test]
+    fn channel_owned_payload() {
+        lock().unwrap();
+        let (s, r) = channel::<String>();
+        std::thread::spawn(move || s.send(String::from("hello from a thread")))
+            .join()
+            .unwrap();
+        let mut received = None;
+        for _ in 0..100 {
+            let _ = wait_for(0.01);
+            received = r.recv();
+            if received.is_some() {
+                break;
+            }
+        }
+        assert_eq!(received.as_deref(), Some("hello from a thread"));
+    }
+    #[test]
+    fn channel_drain() {
+        lock().unwrap();
+        let (s, r) = channel::<u64>();
+        for i in 0..3 {
+            s.send(i);
+        }
+        let mut received: Vec<u64> = vec![];
+        for _ in 0..100 {
+            let _ = wait_for(0.01);
+            received.extend(r.try_iter());
+            if received.len() >= 3 {
+                break;
+            }
+        }
+        assert_eq!(received.len(), 3);
+    }
+    #[test]
+    fn distinct_channels() {
+        lock().unwrap();
+        let (s1, r1) = channel::<i32>();
+        let (_s2, r2) = channel::<i32>();
+        assert_ne!(r1.id, r2.id);
+        s1.send(42);
+        let mut received = None;
+        for _ in 0..100 {
+            let _ = wait_for(0.01);
+            assert_eq!(r2.recv(), None);
+            received = r1.recv();
+            if received.is_some() {
+                break;
+            }
+        }
+        assert_eq!(received, Some(42));
+    }
+    #[test]
+    fn interleaved_channels() {
+        lock().unwrap();
+        let (s1, r1) = channel::<String>();
+        let (s2, r2) = channel::<String>();
+        for i in 0..40 {
+            s1.send(format!("a{}", i));
+            s2.send(format!("b{}", i));
+        }
+        let mut a: Vec<String> = vec![];
+        let mut b: Vec<String> = vec![];
+        for _ in 0..100 {
+            let _ = wait_for(0.01);
+            // Draining the first channel parks the second's messages until r2 asks for them
+            a.extend(r1.try_iter());
+            b.extend(r2.try_iter());
+            if a.len() >= 40 && b.len() >= 40 {
+                break;
+            }
+        }
+        assert_eq!(a, (0..40).map(|i| format!("a{}", i)).collect::<Vec<_>>());
+        assert_eq!(b, (0..40).map(|i| format!("b{}", i)).collect::<Vec<_>>());
+    }
+    #[test]
+    fn channel_payload_dropped() {
+        static DROPS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+        struct Counted(String);
+        impl Drop for Counted {
+            fn drop(&mut self) {
+                assert!(!self.0.is_empty());
+                DROPS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            }
+        }
+        lock().unwrap();
+        let (s, r) = channel::<Counted>();
+        let (other, _) = channel::<Counted>();
+        for i in 0..20 {
+            s.send(Counted(i.to_string()));
+            other.send(Counted(i.to_string()));
+        }
+        let mut received = 0;
+        for _ in 0..100 {
+            let _ = wait_for(0.01);
+            received += r.try_iter().count();
+            if received >= 20 {
+                break;
+            }
+        }
+        // The received payloads were dropped by count(), the other channel's are kept for its receiver
+        assert_eq!(received, 20);
+        assert_eq!(DROPS.load(std::sync::atomic::Ordering::Relaxed), 20);
+    }
+    #[test]
+    fn handler_removal() {
+        fn intercept(ev: Event) -> bool {
+            ev == Event::Shortcut
+        }
+        add_handler(intercept);
+        assert!(EventBuilder::new(Event::Shortcut).dispatch());
+        remove_handler(intercept);
+        assert!(!EventBuilder::new(Event::Shortcut).dispatch());
+    }
+    #[test]
+    fn handler_event_code() {
+        fn ignore(_ev: Event) -> bool {
+            false
+        }
+        fn intercept(ev: Event) -> bool {
+            ev as i32 == 12
+        }
+        add_handler(ignore);
+        add_handler(intercept);
+        assert!(EventBuilder::new(Event::Shortcut).dispatch());
+        remove_handler(intercept);
+        remove_handler(ignore);
+    }
+    #[test]
+    fn screen_bounds() {
+        for n in 0..screen_count() {
+            let (_, _, w, h) = screen_xywh(n);
+            assert!(w > 0 && h > 0);
+        }
+    }
+    #[test]
+    fn clipboard_round_trip() {
+        copy("hello");
+        assert_eq!(clipboard_text(), "hello");
+    }
+    #[test]
+    fn copy_multi_text_only() {
+        copy("before");
+        assert!(!copy_multi(&[("text/html", b"<b>html</b>")]));
+        assert!(!copy_multi(&[]));
+        assert_eq!(clipboard_text(), "before");
+        assert!(copy_multi(&[
+            ("text/html", b"<b>rich</b>"),
+            ("text/plain;charset=utf-8", b"rich")
+        ]));
+        assert_eq!(clipboard_text(), "rich");
+    }
+    #[test]
+    fn remote_display() {
+        assert!(!display_is_remote(":0", true));
+        assert!(!display_is_remote("unix:0.0", false));
+        assert!(!display_is_remote("localhost:0", true));
+        assert!(display_is_remote("localhost:10.0", true));
+        assert!(!display_is_remote("localhost:10.0", false));
+        assert!(display_is_remote("buildhost:0", false));
+    }
+    #[test]
+    fn idle_ticks() {
+        let ticks = std::rc::Rc::new(std::cell::Cell::new(0));
+        let t = ticks.clone();
+        let handle = add_idle(Box::new(move || t.set(t.get() + 1)));
+        for _ in 0..5 {
+            let _ = wait_for(0.01);
+        }
+        assert!(ticks.get() > 0);
+        remove_idle(handle);
+        assert!(!handle.is_active());
+        let after = ticks.get();
+        let _ = wait_for(0.01);
+        assert_eq!(ticks.get(), after);
+    }
+    #[test]
+    fn screen_size_units() {
+        let (w, h) = screen_size();
+        let (_, _, sw, sh) = screen_xywh(0);
+        assert!(screen_scale(0) > 0.0);
+        assert!(w > 0.0 && w <= sw as f64);
+        assert!(h > 0.0 && h <= sh as f64);
+    }
+    #[test]
+    #[ignore = "needs the DejaVu fonts installed"]
+    fn multiple_loaded_fonts() {
+        let serif_path = std::path::Path::new("/usr/share/fonts/truetype/dejavu/DejaVuSerif.ttf");
+        let mono_path =
+            std::path::Path::new("/usr/share/fonts/truetype/dejavu/DejaVuSansMono-Bold.ttf");
+        let _app = App::default();
+        let (serif, _) = load_font(serif_path).unwrap();
+        let (mono, _) = load_font(mono_path).unwrap();
+        assert!(serif.bits() >= 16);
+        assert_ne!(serif, mono);
+        let mut f1 = crate::frame::Frame::new(0, 0, 100, 30, "Serif");
+        let mut f2 = crate::frame::Frame::new(0, 30, 100, 30, "Mono");
+        f1.set_label_font(serif);
+        f2.set_label_font(mono);
+        assert_eq!(f1.label_font(), serif);
+        assert_eq!(f2.label_font(), mono);
+        assert_eq!(load_font(serif_path).unwrap().0, serif);
+    }
+    #[test]
+    fn unload_unknown_font() {
+        let path = std::path::Path::new("/no/such/font.ttf");
+        assert!(unload_font(path).is_err());
+    }
+    #[test]
+    #[ignore = "needs the DejaVu fonts installed"]
+    fn unloaded_font() {
+        let path = std::path::Path::new("/usr/share/fonts/truetype/dejavu/DejaVuSerif-Italic.ttf");
+        let _app = App::default();
+        let (_, name) = load_font(path).unwrap();
+        assert!(font_index(&name).is_some());
+        unload_font(path).unwrap();
+        assert_eq!(font_index(&name), None);
+        assert!(unload_font(path).is_err());
+    }
+    #[test]
+    #[ignore = "needs the DejaVu fonts installed"]
+    fn unload_keeps_other_fonts() {
+        let serif_path =
+            std::path::Path::new("/usr/share/fonts/truetype/dejavu/DejaVuSerif-Bold.ttf");
+        let sans_path =
+            std::path::Path::new("/usr/share/fonts/truetype/dejavu/DejaVuSans-Bold.ttf");
+        let _app = App::default();
+        let (_, serif_name) = load_font(serif_path).unwrap();
+        let (sans, sans_name) = load_font(sans_path).unwrap();
+        unload_font(serif_path).unwrap();
+        assert_eq!(font_index(&serif_name), None);
+        assert_eq!(Font::by_name(&sans_name), sans);
+        crate::draw::set_font(sans, 20);
+        assert!(crate::draw::width("Still loaded") > 0.0);
+        unload_font(sans_path).unwrap();
+    }
+    #[test]
+    fn delay_timing() {
+        let now = std::time::Instant::now();
+        delay(50);
+        let elapsed = now.elapsed().as_millis();
+        // Loaded CI machines can oversleep by a lot, only the lower bound is reliable
+        assert!(elapsed >= 50);
+        assert!(elapsed < 1000);
+    }
+    #[test]
+    fn mouse_button_codes() {
+        assert_eq!(MouseButton::from_i32(1), MouseButton::Left);
+        assert_eq!(MouseButton::from_i32(2), MouseButton::Middle);
+        assert_eq!(MouseButton::from_i32(3), MouseButton::Right);
+        assert_eq!(MouseButton::from_i32(4), MouseButton::Back);
+        assert_eq!(MouseButton::from_i32(5), MouseButton::Forward);
+    }
+    #[test]
+    fn no_dnd_text() {
+        assert_eq!(event_dnd_text(), "");
+    }
+    #[test]
+    fn wait_times_out() {
+        if cfg!(target_os = "windows") {
+            return;
+        }
+        assert!(!wait_timeout(0.01).unwrap());
+    }
+    #[test]
+    fn default_font_size() {
+        let old = font_size();
+        set_font_size(18);
+        assert_eq!(font_size(), 18);
+        let frame = crate::frame::Frame::new(0, 0, 100, 30, "Big");
+        set_font_size(old);
+        assert_eq!(frame.label_size(), 18);
+    }
+    #[test]
+    fn key_not_down() {
+        assert!(!event_key_down(Key::ScrollLock));
+    }
+    #[test]
+    fn event_dispatch_hook() {
+        static SEEN: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+        fn count(_ev: Event, _win: WidgetPtr) -> bool {
+            SEEN.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            true
+        }
+        set_event_dispatch(count);
+        let _ = EventBuilder::new(Event::Move).dispatch();
+        let _ = EventBuilder::new(Event::Shortcut).dispatch();
+        reset_event_dispatch();
+        assert!(SEEN.load(std::sync::atomic::Ordering::Relaxed) >= 2);
+    }
+    #[test]
+    fn dispatch_with_focus_follows_mouse() {
+        static SEEN: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+        fn count(_ev: Event, _win: WidgetPtr) -> bool {
+            SEEN.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            true
+        }
+        set_focus_follows_mouse(true);
+        set_event_dispatch(count);
+        set_focus_follows_mouse(true);
+        let _ = EventBuilder::new(Event::Move).dispatch();
+        let after_enable = SEEN.load(std::sync::atomic::Ordering::Relaxed);
+        assert!(after_enable >= 1);
+        set_focus_follows_mouse(false);
+        let _ = EventBuilder::new(Event::Move).dispatch();
+        reset_event_dispatch();
+        assert!(SEEN.load(std::sync::atomic::Ordering::Relaxed) > after_enable);
+    }
+    #[test]
+    fn grab_release() {
+        let mut win = Window::new(0, 0, 100, 100, "Popup");
+        win.end();
+        win.show();
+        set_grab(&win);
+        assert!(grab().is_some());
+        release();
+        assert!(grab().is_none());
+        win.hide();
+    }
+    #[test]
+    fn palette_colors() {
+        // Color::FrameDefault is the palette's background and Color::BackGround its text background
+        let old = (
+            get_color(Color::FrameDefault),
+            get_color(Color::BackGround),
+            get_color(Color::ForeGround),
+        );
+        set_background(40, 44, 52);
+        set_background2(30, 33, 39);
+        set_foreground(220, 223, 228);
+        redraw();
+        let (r, g, b) = get_color(Color::FrameDefault);
+        assert!(
+            (r as i32 - 40).abs() <= 1 && (g as i32 - 44).abs() <= 1 && (b as i32 - 52).abs() <= 1
+        );
+        assert_eq!(get_color(Color::BackGround), (30, 33, 39));
+        assert_eq!(get_color(Color::ForeGround), (220, 223, 228));
+        let ((r, g, b), (r2, g2, b2), (fr, fg, fb)) = old;
+        set_background(r, g, b);
+        set_background2(r2, g2, b2);
+        set_foreground(fr, fg, fb);
+        assert_eq!(get_color(Color::ForeGround), old.2);
+    }
+    #[test]
+    fn visual_mode_bits() {
+        let mode = Mode::Rgb8 | Mode::Double | Mode::Depth;
+        assert_eq!(mode.bits(), 64 | 2 | 16);
+        assert!(mode.contains(Mode::Double));
+        assert!(!mode.contains(Mode::Alpha));
+        assert_eq!(mode & Mode::Depth, Mode::Depth);
+        assert_eq!(mode & Mode::Stencil, Mode::Rgb);
+    }
+    #[test]
+    fn focus_stack() {
+        let first = crate::input::Input::new(0, 0, 100, 30, "");
+        let second = crate::input::Input::new(0, 40, 100, 30, "");
+        push_focus(&first);
+        set_focus(&second);
+        assert!(pop_focus());
+        let focused = focus().unwrap();
+        assert_eq!(unsafe { focused.as_widget_ptr() }, unsafe { first.as_widget_ptr() });
+        assert!(!pop_focus());
+    }
+    #[test]
+    fn widget_under_mouse() {
+        let below: Option<crate::widget::Widget> = belowmouse();
+        if let Some(below) = below {
+            assert!(!below.was_deleted());
+        }
+    }
+    #[test]
+    fn awake_frees_closure() {
+        struct DropFlag(std::sync::Arc<std::sync::atomic::AtomicBool>);
+        impl Drop for DropFlag {
+            fn drop(&mut self) {
+                self.0.store(true, std::sync::atomic::Ordering::SeqCst);
+            }
+        }
+        lock().unwrap();
+        let dropped = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let flag = DropFlag(dropped.clone());
+        awake(Box::new(move || {
+            let _ = &flag;
+        }));
+        for _ in 0..100 {
+            let _ = wait_for(0.01);
+            if dropped.load(std::sync::atomic::Ordering::SeqCst) {
+                break;
+            }
+        }
+        assert!(dropped.load(std::sync::atomic::Ordering::SeqCst));
+    }
+    #[test]
+    fn check_runs() {
+        let checks = std::rc::Rc::new(std::cell::Cell::new(0));
+        let c = checks.clone();
+        let handle = add_check(Box::new(move || c.set(c.get() + 1)));
+        for _ in 0..3 {
+            let _ = wait_for(0.01);
+        }
+        assert!(checks.get() > 0);
+        remove_check(handle);
+        assert!(!handle.is_active());
+    }
+    #[test]
+    fn custom_box_type() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static DRAWN: AtomicUsize = AtomicUsize::new(0);
+        fn flat_box(x: i32, y: i32, w: i32, h: i32, c: Color) {
+            DRAWN.fetch_add(1, Ordering::SeqCst);
+            crate::draw::draw_rect_fill(x, y, w, h, c);
+        }
+        set_box_type(FrameType::EmbossedBox, flat_box);
+        let mut win = Window::new(0, 0, 100, 100, "Boxes");
+        let mut frame = crate::frame::Frame::new(10, 10, 80, 80, "");
+        frame.set_frame(FrameType::EmbossedBox);
+        win.end();
+        win.show();
+        let now = std::time::Instant::now();
+        while DRAWN.load(Ordering::SeqCst) == 0 && now.elapsed().as_secs() < 2 {
+            let _ = wait_for(0.01);
+        }
+        win.hide();
+        assert!(DRAWN.load(Ordering::SeqCst) > 0);
+    }
+    #[test]
+    fn check_drives_loop() {
+        let mut win = Window::new(0, 0, 100, 100, "Check");
+        win.end();
+        win.show();
+        assert!(check());
+        flush();
+        assert!(win.shown());
+        win.hide();
+    }
+    #[test]
+    fn resize_settled_once() {
+        let mut win = Window::new(0, 0, 100, 100, "Settle");
+        win.end();
+        let settled = std::rc::Rc::new(std::cell::RefCell::new(vec![]));
+        let s = settled.clone();
+        on_resize_settled(
+            &mut win,
+            std::time::Duration::from_millis(20),
+            Box::new(move |w, h| s.borrow_mut().push((w, h))),
+        );
+        win.resize(0, 0, 150, 120);
+        win.resize(0, 0, 200, 140);
+        let now = std::time::Instant::now();
+        while settled.borrow().is_empty() && now.elapsed().as_secs() < 2 {
+            let _ = wait_for(0.01);
+        }
+        assert_eq!(*settled.borrow(), vec![(200, 140)]);
+    }
+    #[test]
+    fn tooltip_globals() {
+        let old = tooltip_delay();
+        set_tooltip_delay(2.5);
+        assert_eq!(tooltip_delay(), 2.5);
+        set_tooltip_color(Color::Yellow);
+        set_tooltip_text_color(Color::Black);
+        set_tooltip_delay(old);
+    }
+    #[test]
+    fn primary_selection() {
+        let inp = crate::input::Input::new(0, 0, 100, 30, "");
+        set_selection("primary");
+        paste_primary(&inp);
+        // Pasting is synchronous while this app owns the selection, but give X11 a moment otherwise
+        let now = std::time::Instant::now();
+        while inp.value().is_empty() && now.elapsed().as_secs() < 1 {
+            let _ = wait_for(0.01);
+        }
+        assert_eq!(inp.value(), "primary");
+    }
+    #[test]
+    fn click_counts() {
+        set_event_clicks(0);
+        assert_eq!(event_clicks_num(), 0);
+        assert!(!event_clicks());
+        set_event_clicks(1);
+        assert_eq!(event_clicks_num(), 1);
+        assert!(event_clicks());
+        set_event_clicks(2);
+        assert_eq!(event_clicks_num(), 2);
+        set_event_clicks(0);
+    }
+    #[test]
+    fn default_icon() {
+        let icon = crate::image::RgbImage::new(&[200u8; 16 * 16 * 3], 16, 16, 3).unwrap();
+        set_default_icon(&icon);
+        let mut win = Window::new(0, 0, 100, 100, "Icon");
+        win.end();
+        win.show();
+        assert!(win.shown());
+        win.hide();
+    }
+    /// Held by the tests expecting `run` to return their own callback's panic
+    static PANIC_TESTS: std::sync::Mutex<()> = std::sync::Mutex::new(());
+    #[test]
+    fn callback_panic_ends_run() {
+        let _serial = PANIC_TESTS.lock().unwrap_or_else(|e| e.into_inner());
+        let mut win = Window::new(0, 0, 100, 100, "Panic");
+        win.end();
+        win.show();
+        add_timeout(0.01, Box::new(|| panic!("boom")));
+        match run() {
+            Err(FltkError::CallbackPanic(payload)) => {
+                assert_eq!(payload.downcast_ref::<&str>(), Some(&"boom"))
+            }
+            _ => panic!("run should return the callback's panic"),
+        }
+        win.hide();
+    }
+    #[test]
+    fn handler_panic_ends_run() {
+        fn boom(ev: Event) -> bool {
+            if ev == Event::Shortcut {
+                panic!("handler boom");
+            }
+            false
+        }
+        let _serial = PANIC_TESTS.lock().unwrap_or_else(|e| e.into_inner());
+        let mut win = Window::new(0, 0, 100, 100, "Panic");
+        win.end();
+        win.show();
+        add_handler(boom);
+        add_timeout(
+            0.01,
+            Box::new(|| {
+                let _ = EventBuilder::new(Event::Shortcut).dispatch();
+            }),
+        );
+        let res = run();
+        remove_handler(boom);
+        win.hide();
+        match res {
+            Err(FltkError::CallbackPanic(payload)) => {
+                assert_eq!(payload.downcast_ref::<&str>(), Some(&"handler boom"))
+            }
+            _ => panic!("run should return the handler's panic"),
+        }
+    }
+    #[test]
+    #[cfg(not(target_os = "macos"))]
+    fn display_opened() {
+        open_display();
+        assert!(!display().is_null());
+    }
+    #[test]
+    fn quit_hooks_after_close_callbacks() {
+        static RAN: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+        let mut stubborn = Window::new(0, 0, 100, 100, "Stubborn");
+        stubborn.end();
+        stubborn.show();
+        // Keeps the window open, as a close callback asking for confirmation would
+        stubborn.set_callback(Box::new(|| ()));
+        let mut win = Window::new(0, 0, 100, 100, "Closing");
+        win.end();
+        win.show();
+        let mut w = win.clone();
+        win.set_callback(Box::new(move || w.hide()));
+        on_quit(Box::new(|| {
+            RAN.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        }));
+        assert!(!close_windows(vec![stubborn.clone(), win.clone()]));
+        assert!(!win.shown());
+        assert_eq!(RAN.load(std::sync::atomic::Ordering::Relaxed), 0);
+        stubborn.hide();
+        win.show();
+        assert!(close_windows(vec![stubborn, win]));
+        assert_eq!(RAN.load(std::sync::atomic::Ordering::Relaxed), 1);
+    }
+    #[test]
+    fn font_suffixes() {
+        assert_eq!(closest_font(&fonts(), "Courier Italic"), Some((2, false)));
+        assert_eq!(closest_font(&fonts(), "DejaVu Sans Bold Oblique"), Some((4, false)));
+        assert_eq!(closest_font(&fonts(), "Times"), None);
     }
 }
@@ -231,4 +231,21 @@ mod button {
         but.set_tooltip("tooltip");
         assert!(but.tooltip().unwrap() == "tooltip");
     }
+    #[test]
+    fn synthetic_event() {
+        use crate::app::*;
+        let mut but = Button::new(0, 0, 80, 40, "hello");
+        EventBuilder::new(Event::Push)
+            .with_pos(10, 12)
+            .with_button(MouseButton::Right)
+            .dispatch_to(&mut but);
+        assert_eq!(event_coords(), (10, 12));
+        assert!(event_button_is(MouseButton::Right));
+        EventBuilder::new(Event::KeyDown)
+            .with_key(Key::Enter)
+            .with_text("\r")
+            .dispatch_to(&mut but);
+        assert!(event_key() == Key::Enter);
+        assert_eq!(event_text(), "\r");
+    }
 }
@@ -231,6 +231,23 @@ pub fn input(x: i32, y: i32, txt: &str, deflt: &str) -> Option<String> {
     }
 }
 
+/// Sets the window title of the next message, alert, choice or input box only
+pub fn message_title(title: &str) {
+    unsafe {
+        let title = CString::safe_new(title).unwrap();
+        Fl_message_title(title.as_ptr())
+    }
+}
+
+/// Sets the window title used by message, alert, choice and input boxes
+/// when no title was set with `message_title`
+pub fn message_title_default(title: &str) {
+    unsafe {
+        let title = CString::safe_new(title).unwrap();
+        Fl_message_title_default(title.as_ptr())
+    }
+}
+
 /// Shows an input box, but with hidden string
 pub fn password(x: i32, y: i32, txt: &str, deflt: &str) -> Option<String> {
     unsafe {
@@ -483,7 +500,7 @@ impl FileChooser {
             unsafe extern "C" fn shim(_arg1: *mut Fl_File_Chooser, data: *mut raw::c_void) {
                 let a: *mut Box<dyn FnMut()> = data as *mut Box<dyn FnMut()>;
                 let f: &mut (dyn FnMut()) = &mut **a;
-                let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f()));
+                crate::app::catch_callback_panic(|| f());
             }
             let a: *mut Box<dyn FnMut()> = Box::into_raw(Box::new(cb));
             let data: *mut raw::c_void = a as *mut raw::c_void;
@@ -685,7 +702,7 @@ impl FileChooser {
     /// Sets the text font of the file chooser
     pub fn set_textfont(&mut self, f: Font) {
         assert!(!self._inner.is_null());
-        unsafe { Fl_File_Chooser_set_textfont(self._inner, f as i32) }
+        unsafe { Fl_File_Chooser_set_textfont(self._inner, f.bits()) }
     }
 
     /// Gets the text font of the file chooser
@@ -491,7 +491,7 @@ pub fn end_complex_polygon() {
 
 /// Sets the current font, which is then used in various drawing routines
 pub fn set_font(face: Font, fsize: u32) {
-    unsafe { Fl_set_font(face as i32, fsize as i32) }
+    unsafe { Fl_set_font(face.bits(), fsize as i32) }
 }
 
 /// Gets the current font, which is used in various drawing routines
@@ -512,7 +512,7 @@ pub fn height() -> i32 {
 /// Sets the line spacing for the current font
 pub fn set_height(font: Font, size: u32) {
     unsafe {
-        Fl_set_height(font as i32, size as i32);
+        Fl_set_height(font.bits(), size as i32);
     }
 }
 
@@ -657,7 +657,7 @@ pub fn set_spot<Win: WindowExt>(font: Font, size: u32, x: i32, y: i32, w: i32, h
     unsafe {
         assert!(!win.was_deleted());
         Fl_set_spot(
-            font as i32,
+            font.bits(),
             size as i32,
             x,
             y,
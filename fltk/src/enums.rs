@@ -98,45 +98,59 @@ pub enum Align {
     Right = 8,
 }
 
-/// Defines fonts used by FLTK
-#[repr(i32)]
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub enum Font {
-    Helvetica = 0,
-    HelveticaBold = 1,
-    HelveticaItalic = 2,
-    HelveticaBoldItalic = 3,
-    Courier = 4,
-    CourierBold = 5,
-    CourierItalic = 6,
-    CourierBoldItalic = 7,
-    Times = 8,
-    TimesBold = 9,
-    TimesItalic = 10,
-    TimesBoldItalic = 11,
-    Symbol = 12,
-    Screen = 13,
-    ScreenBold = 14,
-    Zapfdingbats = 15,
-}
+/// Defines fonts used by FLTK.
+/// Besides the 16 builtin fonts, any font index is valid, e.g. fonts loaded with `app::load_font`
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Font(i32);
 
+#[allow(non_upper_case_globals)]
 impl Font {
+    pub const Helvetica: Font = Font(0);
+    pub const HelveticaBold: Font = Font(1);
+    pub const HelveticaItalic: Font = Font(2);
+    pub const HelveticaBoldItalic: Font = Font(3);
+    pub const Courier: Font = Font(4);
+    pub const CourierBold: Font = Font(5);
+    pub const CourierItalic: Font = Font(6);
+    pub const CourierBoldItalic: Font = Font(7);
+    pub const Times: Font = Font(8);
+    pub const TimesBold: Font = Font(9);
+    pub const TimesItalic: Font = Font(10);
+    pub const TimesBoldItalic: Font = Font(11);
+    pub const Symbol: Font = Font(12);
+    pub const Screen: Font = Font(13);
+    pub const ScreenBold: Font = Font(14);
+    pub const Zapfdingbats: Font = Font(15);
+
+    /// Returns the FLTK index of the font
+    pub const fn bits(self) -> i32 {
+        self.0
+    }
+
     /// Returns a font by index, can be queried via the app::get_font_names()
     pub fn by_index(idx: usize) -> Font {
         unsafe {
             if idx < FONTS.len() {
-                std::mem::transmute(idx as i32)
+                Font(idx as i32)
             } else {
                 Font::Helvetica
             }
         }
     }
 
-    /// Gets the font by its name, can be queried via the app::get_font_names()
+    /// Gets the font by its name, can be queried via the app::get_font_names().
+    /// The lookup ignores case and falls back to the closest match, or Helvetica if there's none
     pub fn by_name(name: &str) -> Font {
-        match font_index(name) {
-            Some(val) => Font::by_index(val),
-            None => Font::Helvetica,
+        Font::by_name_checked(name).0
+    }
+
+    /// Gets the font closest to `name` along with whether the match was exact (ignoring case).
+    /// Falls back to Helvetica if no font matches
+    pub fn by_name_checked(name: &str) -> (Font, bool) {
+        match closest_font_index(name) {
+            Some((val, exact)) => (Font::by_index(val), exact),
+            None => (Font::Helvetica, false),
         }
     }
 }
@@ -238,6 +252,83 @@ pub enum Event {
     ZoomEvent,
 }
 
+/// Defines the mouse buttons reported by FLTK
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum MouseButton {
+    Left = 1,
+    Middle = 2,
+    Right = 3,
+    Back = 4,
+    Forward = 5,
+}
+
+impl MouseButton {
+    /// Gets a MouseButton from FLTK's button code, unknown codes map to Left
+    pub fn from_i32(val: i32) -> MouseButton {
+        match val {
+            2 => MouseButton::Middle,
+            3 => MouseButton::Right,
+            4 => MouseButton::Back,
+            5 => MouseButton::Forward,
+            _ => MouseButton::Left,
+        }
+    }
+}
+
+/// Defines why a widget's callback was called, mirroring FLTK's Fl_Callback_Reason
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum CallbackReason {
+    Unknown = 0,
+    Selected,
+    Deselected,
+    Reselected,
+    Opened,
+    Closed,
+    Dragged,
+    Cancelled,
+    Changed,
+    GotFocus,
+    LostFocus,
+    Released,
+    EnterKey,
+    User = 32,
+}
+
+impl CallbackReason {
+    /// Gets a CallbackReason from FLTK's reason code, unknown codes map to Unknown
+    pub fn from_i32(val: i32) -> CallbackReason {
+        use CallbackReason::*;
+        match val {
+            1 => Selected,
+            2 => Deselected,
+            3 => Reselected,
+            4 => Opened,
+            5 => Closed,
+            6 => Dragged,
+            7 => Cancelled,
+            8 => Changed,
+            9 => GotFocus,
+            10 => LostFocus,
+            11 => Released,
+            12 => EnterKey,
+            32 => User,
+            _ => Unknown,
+        }
+    }
+}
+
+/// Defines the source of a paste operation
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ClipboardSource {
+    /// The primary selection, i.e. the last selected text on X11. Elsewhere it's the clipboard
+    Selection = 0,
+    /// The clipboard, filled by explicit copy operations
+    Clipboard = 1,
+}
+
 /// Defines the inputted virtual keycode
 #[repr(i32)]
 #[derive(Copy, Clone, PartialEq)]
@@ -354,9 +445,18 @@ pub enum Shortcut {
     CapsLock = 0x00020000,
     Ctrl = 0x00040000,
     Alt = 0x00080000,
+    Meta = 0x00400000,
 }
 
 impl Shortcut {
+    /// The platform's command modifier, Meta (Cmd) on macOS and Ctrl elsewhere
+    #[allow(non_upper_case_globals)]
+    pub const Command: Shortcut = if cfg!(target_os = "macos") {
+        Shortcut::Meta
+    } else {
+        Shortcut::Ctrl
+    };
+
     /// Create a shortcut from a char
     pub fn from_char(c: char) -> Shortcut {
         Shortcut::None | c
@@ -399,6 +499,44 @@ pub enum TextCursor {
     Simple,
 }
 
+/// Defines the attributes of a style used in `set_highlight_data`, they can be combined with `|`
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TextAttr(i32);
+
+#[allow(non_upper_case_globals)]
+impl TextAttr {
+    /// No attribute
+    pub const None: TextAttr = TextAttr(0);
+    /// Fills the background of the text with the style's `bgcolor`
+    pub const BgColor: TextAttr = TextAttr(1);
+    /// Fills the background up to the end of the line with the style's `bgcolor`
+    pub const BgColorExt: TextAttr = TextAttr(3);
+    /// Underlines the text
+    pub const Underline: TextAttr = TextAttr(4);
+    /// Underlines the text with a grammar squiggle
+    pub const Grammar: TextAttr = TextAttr(8);
+    /// Underlines the text with a spelling squiggle
+    pub const Spelling: TextAttr = TextAttr(12);
+    /// Strikes through the text
+    pub const StrikeThrough: TextAttr = TextAttr(16);
+
+    /// Creates attributes from their FLTK bit representation
+    pub const fn from_bits(bits: i32) -> TextAttr {
+        TextAttr(bits)
+    }
+
+    /// Returns the FLTK bit representation of the attributes
+    pub const fn bits(self) -> i32 {
+        self.0
+    }
+
+    /// Returns whether all the bits of `other` are set
+    pub const fn contains(self, other: TextAttr) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
 /// Defines the cursor styles supported by fltk
 #[repr(i32)]
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -426,22 +564,40 @@ pub enum Cursor {
     None = 255,
 }
 
-/// Defines Fl_Mode types
-#[repr(i32)]
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub enum Mode {
-    Rgb = 0,
-    Index = 1,
-    Double = 2,
-    Accum = 4,
-    Alpha = 8,
-    Depth = 16,
-    Stencil = 32,
-    Rgb8 = 64,
-    MultiSample = 128,
-    Stereo = 256,
-    FakeSingle = 512, // Fake single buffered windows using double-buffer
-    Opengl3 = 1024,
+/// Defines Fl_Mode types, which can be combined with `|` and tested with `&` or `contains`
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Mode(i32);
+
+#[allow(non_upper_case_globals)]
+impl Mode {
+    pub const Rgb: Mode = Mode(0);
+    pub const Index: Mode = Mode(1);
+    pub const Double: Mode = Mode(2);
+    pub const Accum: Mode = Mode(4);
+    pub const Alpha: Mode = Mode(8);
+    pub const Depth: Mode = Mode(16);
+    pub const Stencil: Mode = Mode(32);
+    pub const Rgb8: Mode = Mode(64);
+    pub const MultiSample: Mode = Mode(128);
+    pub const Stereo: Mode = Mode(256);
+    pub const FakeSingle: Mode = Mode(512); // Fake single buffered windows using double-buffer
+    pub const Opengl3: Mode = Mode(1024);
+
+    /// Creates a mode from its FLTK bit representation
+    pub const fn from_bits(bits: i32) -> Mode {
+        Mode(bits)
+    }
+
+    /// Returns the FLTK bit representation of the mode
+    pub const fn bits(self) -> i32 {
+        self.0
+    }
+
+    /// Returns whether all the bits of `other` are set
+    pub const fn contains(self, other: Mode) -> bool {
+        self.0 & other.0 == other.0
+    }
 }
 
 pub trait WidgetType {
@@ -487,7 +643,7 @@ impl std::ops::BitOr<Color> for Color {
 impl std::ops::BitOr<Font> for Font {
     type Output = Font;
     fn bitor(self, rhs: Font) -> Self::Output {
-        unsafe { std::mem::transmute(self as i32 | rhs as i32) }
+        Font(self.0 | rhs.0)
     }
 }
 
@@ -508,6 +664,20 @@ impl std::ops::BitOr<Key> for Key {
 impl std::ops::BitOr<Mode> for Mode {
     type Output = Mode;
     fn bitor(self, rhs: Mode) -> Self::Output {
-        unsafe { std::mem::transmute(self as i32 | rhs as i32) }
+        Mode(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitAnd<Mode> for Mode {
+    type Output = Mode;
+    fn bitand(self, rhs: Mode) -> Self::Output {
+        Mode(self.0 & rhs.0)
+    }
+}
+
+impl std::ops::BitOr<TextAttr> for TextAttr {
+    type Output = TextAttr;
+    fn bitor(self, rhs: TextAttr) -> Self::Output {
+        TextAttr(self.0 | rhs.0)
     }
 }
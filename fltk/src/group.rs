@@ -286,3 +286,21 @@ impl Pack {
         }
     }
 }
+
+#[cfg(test)]
+mod group {
+    use super::*;
+    #[test]
+    fn screen_xywh() {
+        let win = crate::window::Window::new(100, 200, 400, 300, "");
+        let grp1 = Group::new(10, 10, 300, 200, "");
+        let grp2 = Group::new(20, 20, 200, 150, "");
+        let grp3 = Group::new(30, 30, 100, 100, "");
+        let but = crate::button::Button::new(40, 50, 60, 30, "");
+        grp3.end();
+        grp2.end();
+        grp1.end();
+        win.end();
+        assert_eq!(but.screen_xywh(), (140, 250, 60, 30));
+    }
+}
@@ -42,6 +42,18 @@ pub struct FloatInput {
     _tracker: *mut fltk_sys::fl::Fl_Widget_Tracker,
 }
 
+impl FloatInput {
+    /// Returns the value as a number, parsed with `app::parse_decimal` so the app's decimal separator is accepted
+    pub fn value_f64(&self) -> Option<f64> {
+        crate::app::parse_decimal(&self.value())
+    }
+
+    /// Sets the value from a number, formatted with `app::format_decimal` to use the app's decimal separator
+    pub fn set_value_f64(&mut self, val: f64) {
+        self.set_value(&crate::app::format_decimal(val))
+    }
+}
+
 /// Creates a multiline-input widget
 #[derive(WidgetExt, InputExt, Debug)]
 pub struct MultilineInput {
@@ -62,3 +74,38 @@ pub struct SecretInput {
     _inner: *mut Fl_Secret_Input,
     _tracker: *mut fltk_sys::fl::Fl_Widget_Tracker,
 }
+
+#[cfg(test)]
+mod input {
+    use super::*;
+    #[test]
+    fn decimal_separator() {
+        /// Puts back the app-wide separator even if an assertion fails
+        struct Restore(char);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                crate::app::set_decimal_separator(self.0);
+            }
+        }
+        let _restore = Restore(crate::app::decimal_separator());
+        let mut inp = FloatInput::new(0, 0, 0, 0, "");
+        crate::app::set_decimal_separator(',');
+        inp.set_value("2,75");
+        assert_eq!(inp.value_f64(), Some(2.75));
+        inp.set_value_f64(2.5);
+        assert_eq!(inp.value(), "2,5");
+        crate::app::set_decimal_separator('.');
+        assert_eq!(inp.value_f64(), None);
+    }
+    #[test]
+    fn synthetic_keydown() {
+        let inp = Input::new(0, 0, 100, 30, "");
+        // Only sets the event globals, a frame ignores key presses
+        let mut frame = crate::frame::Frame::new(0, 0, 0, 0, "");
+        crate::app::EventBuilder::new(Event::KeyDown)
+            .with_text("a")
+            .dispatch_to(&mut frame);
+        assert!(crate::app::handle(Event::KeyDown, &inp));
+        assert_eq!(inp.value(), "a");
+    }
+}
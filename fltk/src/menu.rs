@@ -160,7 +160,7 @@ impl MenuItem {
     /// Sets the label font of the menu item
     pub fn set_label_font(&mut self, font: Font) {
         assert!(!self.was_deleted() && !self._inner.is_null());
-        unsafe { Fl_Menu_Item_set_label_font(self._inner, font as i32) }
+        unsafe { Fl_Menu_Item_set_label_font(self._inner, font.bits()) }
     }
 
     /// Returns the label size of the menu item
@@ -310,7 +310,7 @@ impl MenuItem {
             ) {
                 let a: *mut Box<dyn FnMut()> = data as *mut Box<dyn FnMut()>;
                 let f: &mut (dyn FnMut()) = &mut **a;
-                let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f()));
+                crate::app::catch_callback_panic(|| f());
             }
             self.unset_callback();
             let a: *mut Box<dyn FnMut()> = Box::into_raw(Box::new(cb));
@@ -321,8 +321,8 @@ impl MenuItem {
     }
 
     /// Use a sender to send a message during callback
-    pub fn emit<T: 'static + Copy + Send + Sync>(&mut self, sender: crate::app::Sender<T>, msg: T) {
-        self.set_callback(Box::new(move || sender.send(msg)));
+    pub fn emit<T: 'static + Clone + Send + Sync>(&mut self, sender: crate::app::Sender<T>, msg: T) {
+        self.set_callback(Box::new(move || sender.send(msg.clone())));
     }
 
     /// Manually unset a callback
@@ -378,6 +378,19 @@ mod menu {
         menu.set_label("cloned");
     }
     #[test]
+    fn menu_font() {
+        crate::app::set_menu_font(Font::Courier, 18);
+        let bar = MenuBar::new(0, 0, 0, 0, "");
+        let chce = Choice::new(0, 0, 0, 0, "");
+        // Restore FLTK's defaults for the menus of other tests
+        crate::app::set_menu_font(Font::Helvetica, 14);
+        assert_eq!(bar.text_font(), Font::Courier);
+        assert_eq!(bar.text_size(), 18);
+        assert_eq!(chce.text_font(), Font::Courier);
+        assert_eq!(chce.text_size(), 18);
+        assert_eq!(MenuBar::new(0, 0, 0, 0, "").text_font(), Font::Helvetica);
+    }
+    #[test]
     fn tooltip() {
         let mut menu = MenuBar::new(0, 0, 0, 0, "hello");
         menu.set_tooltip("tooltip");
@@ -13,6 +13,8 @@ pub enum FltkError {
     NullError(std::ffi::NulError),
     Internal(FltkErrorKind),
     Unknown(String),
+    /// A callback panicked while the event loop was running, holds the panic payload
+    CallbackPanic(Box<dyn std::any::Any + Send>),
 }
 
 /// Error kinds enum for FltkError
@@ -44,6 +46,14 @@ impl fmt::Display for FltkError {
             FltkError::NullError(ref err) => err.fmt(f),
             FltkError::Internal(ref err) => write!(f, "An internal error occured {:?}", err),
             FltkError::Unknown(ref err) => write!(f, "An unknown error occurred {:?}", err),
+            FltkError::CallbackPanic(ref payload) => {
+                let msg = payload
+                    .downcast_ref::<&str>()
+                    .copied()
+                    .or_else(|| payload.downcast_ref::<String>().map(|s| s.as_str()))
+                    .unwrap_or("Box<Any>");
+                write!(f, "A callback panicked {:?}", msg)
+            }
         }
     }
 }
@@ -206,7 +216,7 @@ pub unsafe trait WidgetExt {
     /// Checks whether a widget is capable of taking events
     fn takes_events(&self) -> bool;
     /// Emits a message on callback using a sender
-    fn emit<T: 'static + Copy + Send + Sync>(&mut self, sender: crate::app::Sender<T>, msg: T);
+    fn emit<T: 'static + Clone + Send + Sync>(&mut self, sender: crate::app::Sender<T>, msg: T);
     /// Make the widget take focus
     fn take_focus(&mut self) -> Result<(), FltkError>;
     /// Set the widget to have visible focus
@@ -235,6 +245,8 @@ pub unsafe trait WidgetExt {
     fn as_window(&mut self) -> Option<crate::window::Window>;
     /// Return the widget as a group widget if it's a group widget
     fn as_group(&mut self) -> Option<crate::group::Group>;
+    /// Returns the absolute x, y, width and height of the widget on the screen
+    fn screen_xywh(&self) -> (i32, i32, i32, i32);
     /// INTERNAL: Retakes ownership of the user callback data
     /// # Safety
     /// Can return multiple mutable references to the user_data
@@ -322,7 +334,7 @@ pub unsafe trait WindowExt: GroupExt {
     fn make_modal(&mut self, val: bool);
     /// Makes a window fullscreen
     fn fullscreen(&mut self, val: bool);
-    /// Makes the window current
+    /// Makes the window current, i.e. the target of subsequent drawing calls, see `app::current_window`
     fn make_current(&mut self);
     /// Sets the windows icon
     fn set_icon<T: ImageExt>(&mut self, image: Option<T>);
@@ -356,6 +368,16 @@ pub unsafe trait WindowExt: GroupExt {
     fn iconize(&mut self);
     /// Returns whether the window is fullscreen or not
     fn fullscreen_active(&self) -> bool;
+    /// Sets a callback called with the new x, y, width and height whenever the window is moved or resized,
+    /// replacing any previous one
+    fn resize_callback(&mut self, cb: Box<dyn FnMut(i32, i32, i32, i32)>);
+    /// Resizes the window to fill the work area of its current screen, excluding taskbars.
+    /// The screen is the one containing the window's top-left corner, found with `app::screen_num`,
+    /// which is this crate's equivalent of `Fl::screen_num(x, y)`.
+    /// The prior geometry is remembered for `restore`, and forgotten once the window is deleted
+    fn maximize_to_work_area(&mut self);
+    /// Returns a window maximized with `maximize_to_work_area` to its prior geometry
+    fn restore(&mut self);
 }
 
 /// Defines the methods implemented by all input and output widgets
@@ -448,7 +470,7 @@ pub unsafe trait MenuExt: WidgetExt {
         cb: Box<dyn FnMut()>,
     );
     /// Add a menu item along with an emit (sender and message)
-    fn add_emit<T: 'static + Copy + Send + Sync>(
+    fn add_emit<T: 'static + Clone + Send + Sync>(
         &mut self,
         name: &str,
         shortcut: Shortcut,
@@ -457,7 +479,7 @@ pub unsafe trait MenuExt: WidgetExt {
         msg: T,
     );
     /// Inserts a menu item along with an emit (sender and message)
-    fn insert_emit<T: 'static + Copy + Send + Sync>(
+    fn insert_emit<T: 'static + Clone + Send + Sync>(
         &mut self,
         idx: u32,
         name: &str,
@@ -646,6 +668,15 @@ pub unsafe trait DisplayExt: WidgetExt {
     fn linenumber_align(&self) -> Align;
     /// Checks whether a pixel is within a text selection
     fn in_selection(&self, x: i32, y: i32) -> bool;
+    /// Returns the style buffer set with `set_highlight_data`, if any
+    fn style_buffer(&self) -> Option<TextBuffer>;
+    /// Returns the style table set with `set_highlight_data`, empty if none was set
+    fn highlight_data(&self) -> Vec<StyleTableEntry>;
+    /// Appends `text` to the text buffer and as many `style` characters to the style buffer,
+    /// so both buffers always keep the same length.
+    /// `style` is an ASCII style table character, 'A' being the first entry.
+    /// Until a style buffer is set with `set_highlight_data`, only the text is appended
+    fn append_styled(&mut self, text: &str, style: char);
 }
 
 /// Defines the methods implemented by all browser types
@@ -472,7 +472,7 @@ impl TextBuffer {
                 let a: *mut Box<dyn FnMut(u32, u32, u32, u32, &str)> =
                     data as *mut Box<dyn for<'r> FnMut(u32, u32, u32, u32, &'r str)>;
                 let f: &mut (dyn FnMut(u32, u32, u32, u32, &str)) = &mut **a;
-                let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                crate::app::catch_callback_panic(|| {
                     f(
                         pos as u32,
                         inserted as u32,
@@ -480,7 +480,7 @@ impl TextBuffer {
                         restyled as u32,
                         &temp,
                     )
-                }));
+                });
             }
             let a: *mut Box<dyn FnMut(u32, u32, u32, u32, &str)> = Box::into_raw(Box::new(cb));
             let data: *mut raw::c_void = a as *mut std::ffi::c_void;
@@ -511,7 +511,7 @@ impl TextBuffer {
                 let a: *mut Box<dyn FnMut(u32, u32, u32, u32, &str)> =
                     data as *mut Box<dyn for<'r> FnMut(u32, u32, u32, u32, &'r str)>;
                 let f: &mut (dyn FnMut(u32, u32, u32, u32, &str)) = &mut **a;
-                let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                crate::app::catch_callback_panic(|| {
                     f(
                         pos as u32,
                         inserted as u32,
@@ -519,7 +519,7 @@ impl TextBuffer {
                         restyled as u32,
                         &temp,
                     )
-                }));
+                });
             }
             let a: *mut Box<dyn FnMut(u32, u32, u32, u32, &str)> = Box::into_raw(Box::new(cb));
             let data: *mut raw::c_void = a as *mut std::ffi::c_void;
@@ -566,12 +566,27 @@ pub struct SimpleTerminal {
     _tracker: *mut fltk_sys::fl::Fl_Widget_Tracker,
 }
 
-/// Defines the styles used in the set_highlight_data, which is used with style buffers
+/// Defines the styles used in the set_highlight_data, which is used with style buffers.
+/// Entries can leave out `attr` and `bgcolor` with `..Default::default()`
 #[derive(Debug, Clone, Copy)]
 pub struct StyleTableEntry {
     pub color: Color,
     pub font: Font,
     pub size: u32,
+    pub attr: TextAttr,
+    pub bgcolor: Color,
+}
+
+impl Default for StyleTableEntry {
+    fn default() -> Self {
+        StyleTableEntry {
+            color: Color::ForeGround,
+            font: Font::Helvetica,
+            size: 14,
+            attr: TextAttr::None,
+            bgcolor: Color::White,
+        }
+    }
 }
 
 /// Opaque data containing the style entries
@@ -877,7 +892,9 @@ impl SimpleTerminal {
         unsafe { Fl_Simple_Terminal_history_lines(self._inner) as u32 }
     }
 
-    /// Enables ANSI sequences within the text to control text colors
+    /// Enables ANSI sequences within the text to control text colors.
+    /// When enabled, SGR sequences such as `"\x1b[31m"` (red) and `"\x1b[0m"` (reset) in appended text
+    /// are stripped and colorize the text which follows, so no style buffer has to be maintained by hand
     pub fn set_ansi(&mut self, val: bool) {
         assert!(!self.was_deleted());
         assert!(self.buffer().is_some());
@@ -896,7 +913,7 @@ impl SimpleTerminal {
         assert!(!self.was_deleted());
         assert!(self.buffer().is_some());
         let s = CString::safe_new(s).unwrap();
-        unsafe { Fl_Simple_Terminal_append(self._inner, s.into_raw()) }
+        unsafe { Fl_Simple_Terminal_append(self._inner, s.as_ptr()) }
     }
 
     /// Sets the text of the terminal buffer
@@ -904,7 +921,7 @@ impl SimpleTerminal {
         assert!(!self.was_deleted());
         assert!(self.buffer().is_some());
         let s = CString::safe_new(s).unwrap();
-        unsafe { Fl_Simple_Terminal_set_text(self._inner, s.into_raw()) }
+        unsafe { Fl_Simple_Terminal_set_text(self._inner, s.as_ptr()) }
     }
 
     /// Gets the text of the terminal buffer
@@ -941,10 +958,113 @@ impl SimpleTerminal {
         );
         unsafe { Fl_Simple_Terminal_remove_lines(self._inner, start as i32, count as i32) }
     }
+
+    /// Makes the terminal use the scheme's text background and foreground colors,
+    /// so it follows `app::set_dark_mode` and palette changes instead of keeping its own black background.
+    /// Passing false restores the terminal's black background with white text.
+    /// Colors of ANSI sequences and style tables still override the foreground per character
+    pub fn follow_scheme(&mut self, val: bool) {
+        assert!(!self.was_deleted());
+        let (bg, fg) = if val {
+            (Color::BackGround, Color::ForeGround)
+        } else {
+            (Color::Black, Color::White)
+        };
+        self.set_color(bg);
+        self.set_text_color(fg);
+        self.set_cursor_color(fg);
+        self.redraw();
+    }
 }
 
 #[cfg(test)]
 mod editor {
+    use super::*;
     #[test]
     fn buffer() {}
+    #[test]
+    fn styled_append() {
+        let mut disp = TextDisplay::new(0, 0, 200, 100, "");
+        disp.set_buffer(Some(TextBuffer::default()));
+        let styles = vec![
+            StyleTableEntry {
+                color: Color::Red,
+                font: Font::Courier,
+                size: 14,
+                ..Default::default()
+            },
+            StyleTableEntry {
+                color: Color::Blue,
+                font: Font::Courier,
+                size: 14,
+                ..Default::default()
+            },
+        ];
+        let _tables = disp.set_highlight_data(TextBuffer::default(), styles);
+        disp.append_styled("error: ", 'A');
+        disp.append_styled("file not found\n", 'B');
+        disp.append_styled("héllo", 'A');
+        assert_eq!(disp.buffer().unwrap().length(), disp.style_buffer().unwrap().length());
+    }
+    #[test]
+    fn styled_append_without_highlight_data() {
+        let mut disp = TextDisplay::new(0, 0, 200, 100, "");
+        disp.set_buffer(Some(TextBuffer::default()));
+        disp.append_styled("plain", 'A');
+        assert_eq!(disp.buffer().unwrap().text(), "plain");
+        assert!(disp.style_buffer().is_none());
+    }
+    #[test]
+    fn style_attributes() {
+        let mut disp = TextDisplay::new(0, 0, 200, 100, "");
+        disp.set_buffer(Some(TextBuffer::default()));
+        let underlined = StyleTableEntry {
+            color: Color::Blue,
+            attr: TextAttr::Underline | TextAttr::StrikeThrough,
+            ..Default::default()
+        };
+        assert_eq!(underlined.attr.bits(), 20);
+        assert!(underlined.attr.contains(TextAttr::Underline));
+        assert_eq!(underlined.bgcolor, Color::White);
+        assert_eq!(underlined.font, Font::Helvetica);
+        let _tables = disp.set_highlight_data(TextBuffer::default(), vec![underlined]);
+        disp.append_styled("link", 'A');
+        assert_eq!(disp.style_buffer().unwrap().text(), "AAAA");
+    }
+    #[test]
+    fn highlight_data_round_trip() {
+        let mut term = SimpleTerminal::new(0, 0, 200, 100, "");
+        assert!(term.highlight_data().is_empty());
+        let mut sbuf = TextBuffer::default();
+        let styles = vec![
+            StyleTableEntry {
+                color: Color::Green,
+                font: Font::Courier,
+                size: 16,
+                ..Default::default()
+            },
+            StyleTableEntry {
+                color: Color::Red,
+                attr: TextAttr::StrikeThrough,
+                ..Default::default()
+            },
+        ];
+        let _tables = term.set_highlight_data(sbuf.clone(), styles);
+        sbuf.set_text("AB");
+        assert_eq!(term.style_buffer().unwrap().text(), "AB");
+        let read = term.highlight_data();
+        assert_eq!(read.len(), 2);
+        assert_eq!(read[0].color, Color::Green);
+        assert_eq!(read[0].font, Font::Courier);
+        assert_eq!(read[0].size, 16);
+        assert_eq!(read[1].attr, TextAttr::StrikeThrough);
+    }
+    #[test]
+    fn terminal_ansi() {
+        let mut term = SimpleTerminal::new(0, 0, 200, 100, "");
+        term.set_ansi(true);
+        assert!(term.ansi());
+        term.append("\x1b[31mred\x1b[0m plain");
+        assert_eq!(term.text(), "red plain");
+    }
 }
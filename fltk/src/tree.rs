@@ -632,7 +632,7 @@ impl Tree {
     /// Sets the items' label font
     pub fn set_item_label_font(&mut self, val: Font) {
         assert!(!self.was_deleted());
-        unsafe { Fl_Tree_set_item_labelfont(self._inner, val as i32) }
+        unsafe { Fl_Tree_set_item_labelfont(self._inner, val.bits()) }
     }
 
     /// Gets the items' label size
@@ -1279,7 +1279,7 @@ impl TreeItem {
     /// Sets the label's font
     pub fn set_label_font(&mut self, val: Font) {
         assert!(!self.was_deleted());
-        unsafe { Fl_Tree_Item_set_labelfont(self._inner, val as i32) }
+        unsafe { Fl_Tree_Item_set_labelfont(self._inner, val.bits()) }
     }
 
     /// Gets the label's font
@@ -30,6 +30,25 @@ pub type RawHandle = *mut raw::c_void;
 ))]
 pub type RawHandle = u64;
 
+thread_local! {
+    /// The geometry of windows maximized with `maximize_to_work_area`, along with the window itself,
+    /// whose tracker tells a deleted window apart from a new one allocated at the same address
+    static RESTORE_GEOMETRY: std::cell::RefCell<Vec<(Widget, (i32, i32, i32, i32))>> =
+        const { std::cell::RefCell::new(Vec::new()) };
+}
+
+/// Drops the remembered geometry of windows which were deleted since
+fn forget_deleted_windows(geometry: &mut Vec<(Widget, (i32, i32, i32, i32))>) {
+    geometry.retain_mut(|(w, _)| {
+        if w.was_deleted() {
+            unsafe { w.cleanup() };
+            false
+        } else {
+            true
+        }
+    });
+}
+
 /// Creates a window widget
 #[derive(WidgetExt, GroupExt, WindowExt, Debug)]
 pub struct Window {
@@ -424,14 +443,41 @@ impl GlWindow {
     /// Get the Mode of the GlWindow
     pub fn mode(&self) -> Mode {
         assert!(!self.was_deleted());
-        unsafe { mem::transmute(Fl_Gl_Window_mode(self._inner)) }
+        unsafe { Mode::from_bits(Fl_Gl_Window_mode(self._inner)) }
     }
 
     /// Set the Mode of the GlWindow
     pub fn set_mode(&mut self, mode: Mode) {
         assert!(!self.was_deleted());
         unsafe {
-            Fl_Gl_Window_set_mode(self._inner, mode as i32);
+            Fl_Gl_Window_set_mode(self._inner, mode.bits());
         }
     }
 }
+
+#[cfg(test)]
+mod window {
+    use super::*;
+    #[test]
+    fn restore_after_delete() {
+        let mut win = Window::new(10, 20, 200, 100, "");
+        win.end();
+        win.maximize_to_work_area();
+        win.delete();
+        // Deletion is deferred until the event loop runs
+        let _ = crate::app::wait_for(0.01);
+        let mut other = Window::new(30, 40, 150, 80, "");
+        other.end();
+        other.restore();
+        assert_eq!(
+            (other.x(), other.y(), other.width(), other.height()),
+            (30, 40, 150, 80)
+        );
+        other.maximize_to_work_area();
+        other.restore();
+        assert_eq!(
+            (other.x(), other.y(), other.width(), other.height()),
+            (30, 40, 150, 80)
+        );
+    }
+}